@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use ansi_to_tui::Error;
 use ansi_to_tui::IntoText;
 use ratatui::text::Line;
@@ -21,7 +23,8 @@ pub fn ansi_escape_line(s: &str) -> Line<'static> {
 pub fn ansi_escape(s: &str) -> Text<'static> {
     // to_text() claims to be faster, but introduces complex lifetime issues
     // such that it's not worth it.
-    match s.into_text() {
+    let s = strip_hyperlinks(s);
+    match s.as_ref().into_text() {
         Ok(text) => text,
         Err(err) => match err {
             Error::NomError(message) => {
@@ -37,3 +40,66 @@ pub fn ansi_escape(s: &str) -> Text<'static> {
         },
     }
 }
+
+/// Remove OSC 8 hyperlink wrappers (`ESC ]8;params;url` terminated by BEL or
+/// `ESC \`), keeping the link text. `ansi-to-tui` swallows the text that
+/// follows an `ESC \`-terminated OSC, so links printed by tools such as
+/// `cargo` and `gh` would otherwise vanish.
+fn strip_hyperlinks(s: &str) -> Cow<'_, str> {
+    const OSC8: &str = "\x1b]8;";
+    if !s.contains(OSC8) {
+        return Cow::Borrowed(s);
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find(OSC8) {
+        out.push_str(&rest[..start]);
+        let seq = &rest[start..];
+        let end = [
+            seq.find('\x07').map(|i| i + 1),
+            seq.find("\x1b\\").map(|i| i + 2),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+        match end {
+            Some(end) => rest = &seq[end..],
+            None => {
+                // Unterminated: leave it for ansi-to-tui to deal with.
+                rest = seq;
+                break;
+            }
+        }
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn hyperlink_text_survives_both_terminators() {
+        let line = ansi_escape_line(
+            "see \x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x1b\\ and \x1b]8;id=1;https://a.b\x07here\x1b]8;;\x07!",
+        );
+        assert_eq!(plain(&line), "see docs and here!");
+    }
+
+    #[test]
+    fn hyperlink_text_keeps_surrounding_colors() {
+        let line = ansi_escape_line(
+            "\x1b[31m\x1b]8;;https://example.com\x1b\\red link\x1b]8;;\x1b\\\x1b[0m",
+        );
+        assert_eq!(plain(&line), "red link");
+        assert_eq!(line.spans[0].style.fg, Some(ratatui::style::Color::Red));
+    }
+}
@@ -55,6 +55,7 @@ use crate::environment_context::EnvironmentContext;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::error::SandboxErr;
+use crate::error::UsageLimitReachedError;
 use crate::error::get_error_message_ui;
 use crate::exec::ExecParams;
 use crate::exec::ExecToolCallOutput;
@@ -158,6 +159,11 @@ pub struct CodexSpawnOk {
 
 pub(crate) const INITIAL_SUBMIT_ID: &str = "";
 pub(crate) const SUBMISSION_CHANNEL_CAPACITY: usize = 64;
+/// How long shutdown waits for killed interactive exec sessions to exit.
+const SHUTDOWN_EXEC_KILL_TIMEOUT: Duration = Duration::from_secs(2);
+/// Longest a turn waits for an exhausted rate-limit window to reset before
+/// failing with a usage-limit error instead.
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(5 * 60);
 
 // Model-formatting limits: clients get full streams; oonly content sent to the model is truncated.
 pub(crate) const MODEL_FORMAT_MAX_BYTES: usize = 10 * 1024; // 10 KiB
@@ -253,7 +259,9 @@ impl Codex {
     }
 }
 
+use crate::state::ApprovalScope;
 use crate::state::SessionState;
+use crate::state::approved_commands_file;
 
 /// Context for an initialized model agent
 ///
@@ -394,7 +402,14 @@ impl Session {
         })?;
         let rollout_path = rollout_recorder.rollout_path.clone();
         // Create the mutable state for the Session.
-        let state = SessionState::new();
+        let mut state = SessionState::new();
+        let approvals_path = approved_commands_file(&config.codex_home, &cwd);
+        if let Err(e) = state.load_approved_commands(&approvals_path) {
+            warn!(
+                "failed to load approved commands from {}: {e}",
+                approvals_path.display()
+            );
+        }
 
         // Handle MCP manager result and record any startup failures.
         let (mcp_connection_manager, failed_clients) = match mcp_res {
@@ -642,9 +657,16 @@ impl Session {
         }
     }
 
-    pub async fn add_approved_command(&self, cmd: Vec<String>) {
+    pub(crate) async fn add_approved_command(&self, cmd: Vec<String>, scope: ApprovalScope) {
+        let mut state = self.state.lock().await;
+        if let Err(e) = state.approve_command(cmd, scope) {
+            warn!("failed to save command approval: {e}");
+        }
+    }
+
+    async fn record_recent_command(&self, cmd: Vec<String>) {
         let mut state = self.state.lock().await;
-        state.add_approved_command(cmd);
+        state.record_recent_command(cmd);
     }
 
     /// Records input items: always append to conversation history and
@@ -758,6 +780,18 @@ impl Session {
         self.send_token_count_event(sub_id).await;
     }
 
+    /// How long to hold off before the next model request, with the latest
+    /// rate limits to report; `None` when a request can be sent now.
+    async fn rate_limit_wait(&self) -> Option<(Duration, Option<RateLimitSnapshot>)> {
+        let state = self.state.lock().await;
+        if state.can_send_now() {
+            return None;
+        }
+        state
+            .time_until_sendable()
+            .map(|wait| (wait, state.latest_rate_limits.clone()))
+    }
+
     async fn send_token_count_event(&self, sub_id: &str) {
         let (info, rate_limits) = {
             let state = self.state.lock().await;
@@ -912,6 +946,10 @@ impl Session {
 
         self.on_exec_command_begin(turn_diff_tracker, begin_ctx.clone())
             .await;
+        if !is_apply_patch {
+            self.record_recent_command(exec_args.params.command.clone())
+                .await;
+        }
 
         let result = process_exec_tool_call(
             exec_args.params,
@@ -1363,6 +1401,25 @@ async fn submission_loop(
             Op::Shutdown => {
                 sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
                 info!("Shutting down Codex instance");
+                info!("session usage: {}", sess.state.lock().await.usage_json());
+
+                let unified_exec_manager = &sess.services.unified_exec_manager;
+                if unified_exec_manager.has_running_session().await {
+                    for summary in unified_exec_manager.session_summaries().await {
+                        info!(
+                            "killing unified exec session {} ({}) after {:?}",
+                            summary.session_id,
+                            summary.command.join(" "),
+                            summary.age
+                        );
+                    }
+                }
+                let stuck = unified_exec_manager
+                    .terminate_all(SHUTDOWN_EXEC_KILL_TIMEOUT)
+                    .await;
+                if !stuck.is_empty() {
+                    warn!("unified exec sessions {stuck:?} did not exit before shutdown");
+                }
 
                 // Gracefully flush and shutdown rollout recorder on session end so tests
                 // that inspect the rollout file do not race with the background writer.
@@ -1858,6 +1915,28 @@ async fn run_turn(
 
     let mut retries = 0;
     loop {
+        // Wait out an exhausted rate-limit window instead of spending the
+        // request on a certain 429. Windows that reset too far out (e.g. a
+        // weekly limit) fail the turn rather than hang it.
+        if let Some((wait, rate_limits)) = sess.rate_limit_wait().await {
+            if wait > MAX_RATE_LIMIT_WAIT {
+                return Err(CodexErr::UsageLimitReached(UsageLimitReachedError {
+                    plan_type: None,
+                    resets_in_seconds: Some(wait.as_secs()),
+                    rate_limits,
+                }));
+            }
+            sess.notify_background_event(
+                &sub_id,
+                format!(
+                    "rate limit reached; waiting {}s before sending…",
+                    wait.as_secs()
+                ),
+            )
+            .await;
+            // Interrupting the turn aborts this task, which drops the sleep.
+            tokio::time::sleep(wait).await;
+        }
         match try_run_turn(sess, turn_context, turn_diff_tracker, &sub_id, &prompt).await {
             Ok(output) => return Ok(output),
             Err(CodexErr::Interrupted) => return Err(CodexErr::Interrupted),
@@ -2663,7 +2742,12 @@ async fn handle_container_exec_with_params(
             match decision {
                 ReviewDecision::Approved => (),
                 ReviewDecision::ApprovedForSession => {
-                    sess.add_approved_command(params.command.clone()).await;
+                    sess.add_approved_command(params.command.clone(), ApprovalScope::Session)
+                        .await;
+                }
+                ReviewDecision::ApprovedAlways => {
+                    sess.add_approved_command(params.command.clone(), ApprovalScope::Persistent)
+                        .await;
                 }
                 ReviewDecision::Denied | ReviewDecision::Abort => {
                     return Err(FunctionCallError::RespondToModel(
@@ -2804,12 +2888,20 @@ async fn handle_sandbox_error(
         .await;
 
     match decision {
-        ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {
+        ReviewDecision::Approved
+        | ReviewDecision::ApprovedForSession
+        | ReviewDecision::ApprovedAlways => {
             // Persist this command as pre‑approved for the
             // remainder of the session so future
             // executions skip the sandbox directly.
             // TODO(ragona): Isn't this a bug? It always saves the command in an | fork?
-            sess.add_approved_command(params.command.clone()).await;
+            let scope = if decision == ReviewDecision::ApprovedAlways {
+                ApprovalScope::Persistent
+            } else {
+                ApprovalScope::Session
+            };
+            sess.add_approved_command(params.command.clone(), scope)
+                .await;
             // Inform UI we are retrying without sandbox.
             sess.notify_background_event(&sub_id, "retrying command without sandbox")
                 .await;
@@ -3526,6 +3618,35 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn always_approved_commands_are_reloaded_by_a_new_session() {
+        let (session, turn_context) = make_session_and_context();
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let path = approved_commands_file(codex_home.path(), &turn_context.cwd);
+        session
+            .state
+            .lock()
+            .await
+            .load_approved_commands(&path)
+            .expect("load approvals");
+        let cmd = |s: &str| vec!["bash".to_string(), "-lc".to_string(), s.to_string()];
+
+        session
+            .add_approved_command(cmd("cargo test"), ApprovalScope::Persistent)
+            .await;
+        session
+            .add_approved_command(cmd("rm -rf target"), ApprovalScope::Session)
+            .await;
+
+        let mut next = SessionState::new();
+        next.load_approved_commands(&path)
+            .expect("reload approvals");
+        assert_eq!(
+            next.approved_commands_ref(),
+            &std::collections::HashSet::from([cmd("cargo test")])
+        );
+    }
+
     #[tokio::test]
     async fn abort_regular_task_emits_turn_aborted_only() {
         let (sess, tc, rx) = make_session_and_context_with_rx();
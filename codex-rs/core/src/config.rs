@@ -7,6 +7,8 @@ use crate::config_types::ReasoningSummaryFormat;
 use crate::config_types::SandboxWorkspaceWrite;
 use crate::config_types::ShellEnvironmentPolicy;
 use crate::config_types::ShellEnvironmentPolicyToml;
+use crate::config_types::TranscriptFollow;
+use crate::config_types::TranscriptHighlight;
 use crate::config_types::Tui;
 use crate::config_types::UriBasedFileOpener;
 use crate::git_info::resolve_root_git_project_for_trust;
@@ -49,6 +51,15 @@ pub(crate) const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024; // 32 KiB
 
 pub(crate) const CONFIG_TOML_FILE: &str = "config.toml";
 
+/// Default for `tui.transcript_max_line_chars`.
+const DEFAULT_TRANSCRIPT_MAX_LINE_CHARS: usize = 4000;
+
+/// Default for `tui.pager_max_wrap_width`.
+const DEFAULT_PAGER_MAX_WRAP_WIDTH: u16 = 1000;
+
+/// Default for `tui.pager_wheel_lines`.
+const DEFAULT_PAGER_WHEEL_LINES: usize = 3;
+
 /// Application configuration loaded from disk and merged with overrides.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Config {
@@ -123,6 +134,38 @@ pub struct Config {
     /// and turn completions when not focused.
     pub tui_notifications: Notifications,
 
+    /// Lines of overlap kept between pages when paging in the TUI pager overlays.
+    pub tui_pager_page_overlap: usize,
+
+    /// How the TUI transcript overlay scrolls when new history arrives.
+    pub tui_transcript_follow: TranscriptFollow,
+
+    /// Maximum number of cells retained by the TUI transcript overlay.
+    pub tui_transcript_max_cells: Option<usize>,
+
+    /// Characters kept from a single transcript line before it is truncated.
+    pub tui_transcript_max_line_chars: usize,
+
+    /// Lines scrolled per mouse wheel notch in the TUI pager overlays.
+    pub tui_pager_wheel_lines: usize,
+
+    /// Widest column count the TUI pager overlays wrap lines at.
+    pub tui_pager_max_wrap_width: u16,
+
+    /// Key that dumps a TUI pager overlay's state to a file, e.g. `ctrl+s`.
+    /// `None` keeps the default of F12.
+    pub tui_pager_snapshot_key: Option<String>,
+
+    /// Regex patterns, each with a color, highlighted in the TUI transcript
+    /// overlay.
+    pub tui_transcript_highlights: Vec<TranscriptHighlight>,
+
+    /// Collapse trailing blank lines in TUI command output to a single one.
+    pub tui_trim_trailing_blank_lines: bool,
+
+    /// Capture the mouse while a TUI pager overlay is open.
+    pub tui_pager_mouse_capture: bool,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -1068,6 +1111,48 @@ impl Config {
                 .as_ref()
                 .map(|t| t.notifications.clone())
                 .unwrap_or_default(),
+            tui_pager_page_overlap: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.pager_page_overlap)
+                .unwrap_or_default(),
+            tui_transcript_follow: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.transcript_follow)
+                .unwrap_or_default(),
+            tui_transcript_max_cells: cfg.tui.as_ref().and_then(|t| t.transcript_max_cells),
+            tui_transcript_max_line_chars: cfg
+                .tui
+                .as_ref()
+                .and_then(|t| t.transcript_max_line_chars)
+                .unwrap_or(DEFAULT_TRANSCRIPT_MAX_LINE_CHARS),
+            tui_pager_wheel_lines: cfg
+                .tui
+                .as_ref()
+                .and_then(|t| t.pager_wheel_lines)
+                .unwrap_or(DEFAULT_PAGER_WHEEL_LINES),
+            tui_pager_max_wrap_width: cfg
+                .tui
+                .as_ref()
+                .and_then(|t| t.pager_max_wrap_width)
+                .unwrap_or(DEFAULT_PAGER_MAX_WRAP_WIDTH),
+            tui_pager_snapshot_key: cfg.tui.as_ref().and_then(|t| t.pager_snapshot_key.clone()),
+            tui_transcript_highlights: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.transcript_highlights.clone())
+                .unwrap_or_default(),
+            tui_trim_trailing_blank_lines: cfg
+                .tui
+                .as_ref()
+                .and_then(|t| t.trim_trailing_blank_lines)
+                .unwrap_or(true),
+            tui_pager_mouse_capture: cfg
+                .tui
+                .as_ref()
+                .and_then(|t| t.pager_mouse_capture)
+                .unwrap_or(false),
         };
         Ok(config)
     }
@@ -1229,6 +1314,57 @@ persistence = "none"
         let tui = parsed.tui.expect("config should include tui section");
 
         assert_eq!(tui.notifications, Notifications::Enabled(false));
+        assert_eq!(tui.pager_page_overlap, 0);
+        assert_eq!(tui.transcript_follow, TranscriptFollow::FollowWhenAtBottom);
+        assert_eq!(tui.transcript_max_cells, None);
+        assert_eq!(tui.transcript_max_line_chars, None);
+        assert_eq!(tui.pager_mouse_capture, None);
+        assert_eq!(tui.trim_trailing_blank_lines, None);
+        assert!(tui.transcript_highlights.is_empty());
+        assert_eq!(tui.pager_snapshot_key, None);
+        assert_eq!(tui.pager_max_wrap_width, None);
+        assert_eq!(tui.pager_wheel_lines, None);
+    }
+
+    #[test]
+    fn tui_config_parses_transcript_follow() {
+        let cfg = r#"
+[tui]
+transcript_follow = "never-jump"
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("transcript_follow should parse");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert_eq!(tui.transcript_follow, TranscriptFollow::NeverJump);
+    }
+
+    #[test]
+    fn tui_config_parses_transcript_highlights() {
+        let cfg = r##"
+[tui]
+transcript_highlights = [
+  { pattern = "ERROR", color = "red" },
+  { pattern = "WARN(ING)?", color = "#ffaf00" },
+]
+"##;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("transcript_highlights should parse");
+        let tui = parsed.tui.expect("config should include tui section");
+
+        assert_eq!(
+            tui.transcript_highlights,
+            vec![
+                TranscriptHighlight {
+                    pattern: "ERROR".to_string(),
+                    color: "red".to_string(),
+                },
+                TranscriptHighlight {
+                    pattern: "WARN(ING)?".to_string(),
+                    color: "#ffaf00".to_string(),
+                },
+            ]
+        );
     }
 
     #[test]
@@ -1809,6 +1945,16 @@ model_verbosity = "high"
                 active_profile: Some("o3".to_string()),
                 disable_paste_burst: false,
                 tui_notifications: Default::default(),
+                tui_pager_page_overlap: 0,
+                tui_transcript_follow: TranscriptFollow::default(),
+                tui_transcript_max_cells: None,
+                tui_transcript_max_line_chars: DEFAULT_TRANSCRIPT_MAX_LINE_CHARS,
+                tui_pager_mouse_capture: false,
+                tui_trim_trailing_blank_lines: true,
+                tui_transcript_highlights: Vec::new(),
+                tui_pager_snapshot_key: None,
+                tui_pager_max_wrap_width: DEFAULT_PAGER_MAX_WRAP_WIDTH,
+                tui_pager_wheel_lines: DEFAULT_PAGER_WHEEL_LINES,
             },
             o3_profile_config
         );
@@ -1868,6 +2014,16 @@ model_verbosity = "high"
             active_profile: Some("gpt3".to_string()),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_pager_page_overlap: 0,
+            tui_transcript_follow: TranscriptFollow::default(),
+            tui_transcript_max_cells: None,
+            tui_transcript_max_line_chars: DEFAULT_TRANSCRIPT_MAX_LINE_CHARS,
+            tui_pager_mouse_capture: false,
+            tui_trim_trailing_blank_lines: true,
+            tui_transcript_highlights: Vec::new(),
+            tui_pager_snapshot_key: None,
+            tui_pager_max_wrap_width: DEFAULT_PAGER_MAX_WRAP_WIDTH,
+            tui_pager_wheel_lines: DEFAULT_PAGER_WHEEL_LINES,
         };
 
         assert_eq!(expected_gpt3_profile_config, gpt3_profile_config);
@@ -1942,6 +2098,16 @@ model_verbosity = "high"
             active_profile: Some("zdr".to_string()),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_pager_page_overlap: 0,
+            tui_transcript_follow: TranscriptFollow::default(),
+            tui_transcript_max_cells: None,
+            tui_transcript_max_line_chars: DEFAULT_TRANSCRIPT_MAX_LINE_CHARS,
+            tui_pager_mouse_capture: false,
+            tui_trim_trailing_blank_lines: true,
+            tui_transcript_highlights: Vec::new(),
+            tui_pager_snapshot_key: None,
+            tui_pager_max_wrap_width: DEFAULT_PAGER_MAX_WRAP_WIDTH,
+            tui_pager_wheel_lines: DEFAULT_PAGER_WHEEL_LINES,
         };
 
         assert_eq!(expected_zdr_profile_config, zdr_profile_config);
@@ -2002,6 +2168,16 @@ model_verbosity = "high"
             active_profile: Some("gpt5".to_string()),
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_pager_page_overlap: 0,
+            tui_transcript_follow: TranscriptFollow::default(),
+            tui_transcript_max_cells: None,
+            tui_transcript_max_line_chars: DEFAULT_TRANSCRIPT_MAX_LINE_CHARS,
+            tui_pager_mouse_capture: false,
+            tui_trim_trailing_blank_lines: true,
+            tui_transcript_highlights: Vec::new(),
+            tui_pager_snapshot_key: None,
+            tui_pager_max_wrap_width: DEFAULT_PAGER_MAX_WRAP_WIDTH,
+            tui_pager_wheel_lines: DEFAULT_PAGER_WHEEL_LINES,
         };
 
         assert_eq!(expected_gpt5_profile_config, gpt5_profile_config);
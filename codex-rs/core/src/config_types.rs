@@ -239,6 +239,81 @@ pub struct Tui {
     /// Defaults to `false`.
     #[serde(default)]
     pub notifications: Notifications,
+
+    /// Lines of context kept when paging with PageUp/PageDown in the pager
+    /// overlays. Defaults to `0`.
+    #[serde(default)]
+    pub pager_page_overlap: usize,
+
+    /// How the transcript overlay scrolls when new history arrives. Defaults
+    /// to following only when already scrolled to the bottom.
+    #[serde(default)]
+    pub transcript_follow: TranscriptFollow,
+
+    /// Maximum number of history cells the transcript overlay keeps; older
+    /// cells are replaced by a single "hidden" marker. Unlimited when unset.
+    #[serde(default)]
+    pub transcript_max_cells: Option<usize>,
+
+    /// Characters kept from any single transcript line; the rest is replaced
+    /// by a `…(+N chars)` marker before wrapping. Defaults to `4000`.
+    #[serde(default)]
+    pub transcript_max_line_chars: Option<usize>,
+
+    /// Lines scrolled per mouse wheel notch in the pager overlays while
+    /// `pager_mouse_capture` is on. Defaults to `3`.
+    #[serde(default)]
+    pub pager_wheel_lines: Option<usize>,
+
+    /// Widest column count the pager overlays wrap lines at, bounding the
+    /// wrap cache if a resize reports an absurd width. Defaults to `1000`.
+    #[serde(default)]
+    pub pager_max_wrap_width: Option<u16>,
+
+    /// Key that dumps a pager overlay's state to a file for bug reports, e.g.
+    /// `f12` or `ctrl+s`. Defaults to `f12`.
+    #[serde(default)]
+    pub pager_snapshot_key: Option<String>,
+
+    /// Patterns colored wherever they appear in the transcript overlay,
+    /// applied in order. Empty by default.
+    #[serde(default)]
+    pub transcript_highlights: Vec<TranscriptHighlight>,
+
+    /// Collapse runs of blank lines at the end of command output to one.
+    /// Defaults to `true`.
+    #[serde(default)]
+    pub trim_trailing_blank_lines: Option<bool>,
+
+    /// Capture the mouse while a pager overlay is open, so wheel notches
+    /// scroll by `pager_wheel_lines`. Off by default because capturing
+    /// breaks the terminal's native text selection.
+    #[serde(default)]
+    pub pager_mouse_capture: Option<bool>,
+}
+
+/// Scroll behavior of the transcript overlay when cells are appended.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum TranscriptFollow {
+    /// Keep following new output only while scrolled to the bottom.
+    #[default]
+    FollowWhenAtBottom,
+    /// Jump to the bottom on new output regardless of the scroll position.
+    AlwaysJump,
+    /// Never move the view when new output arrives.
+    NeverJump,
+}
+
+/// A transcript overlay highlight: every match of `pattern` is drawn in
+/// `color`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptHighlight {
+    /// Regular expression matched against each rendered line.
+    pub pattern: String,
+    /// Color name such as `red` or `lightyellow`, or a hex value like
+    /// `#ff8700`.
+    pub color: String,
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq, Default)]
@@ -65,6 +65,16 @@ impl ExecCommandSession {
     pub(crate) fn has_exited(&self) -> bool {
         self.exit_status.load(std::sync::atomic::Ordering::SeqCst)
     }
+
+    /// Signal the child to terminate without waiting for it to exit.
+    pub(crate) fn kill(&self) -> std::io::Result<()> {
+        if let Ok(mut killer_opt) = self.killer.lock()
+            && let Some(killer) = killer_opt.as_mut()
+        {
+            killer.kill()?;
+        }
+        Ok(())
+    }
 }
 
 impl Drop for ExecCommandSession {
@@ -3,7 +3,9 @@ mod session;
 mod turn;
 
 pub(crate) use service::SessionServices;
+pub(crate) use session::ApprovalScope;
 pub(crate) use session::SessionState;
+pub(crate) use session::approved_commands_file;
 pub(crate) use turn::ActiveTurn;
 pub(crate) use turn::RunningTask;
 pub(crate) use turn::TaskKind;
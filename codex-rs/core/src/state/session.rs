@@ -1,21 +1,86 @@
 //! Session-wide mutable state.
 
 use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
 
 use codex_protocol::models::ResponseItem;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use serde_json::json;
+use sha1::Digest;
+use sha1::Sha1;
 
 use crate::conversation_history::ConversationHistory;
 use crate::protocol::RateLimitSnapshot;
+use crate::protocol::RateLimitWindow;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 
+/// How many distinct commands [`SessionState::recent_commands`] remembers.
+pub(crate) const MAX_RECENT_COMMANDS: usize = 20;
+
+/// Default number of entries kept by [`SessionState::token_usage_timeline`].
+pub(crate) const DEFAULT_TOKEN_USAGE_TIMELINE_LEN: usize = 256;
+
+/// How long a command approval lasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ApprovalScope {
+    /// Until this session ends.
+    Session,
+    /// Saved to the project's approvals file and loaded by later sessions.
+    Persistent,
+}
+
+/// One line of the approvals file.
+#[derive(Serialize, Deserialize)]
+struct PersistedApproval {
+    /// [`command_id`] of `command`, so a file can be deduplicated by key.
+    id: String,
+    command: Vec<String>,
+}
+
+/// Stable key for `command`: the SHA-1 of its NUL-joined arguments.
+fn command_id(command: &[String]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(command.join("\0").as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Per-project file holding persistent approvals for sessions started in `cwd`.
+pub(crate) fn approved_commands_file(codex_home: &Path, cwd: &Path) -> PathBuf {
+    let mut hasher = Sha1::new();
+    hasher.update(cwd.to_string_lossy().as_bytes());
+    codex_home
+        .join("approved_commands")
+        .join(format!("{:x}.jsonl", hasher.finalize()))
+}
+
 /// Persistent, session-scoped state previously stored directly on `Session`.
 #[derive(Default)]
 pub(crate) struct SessionState {
     pub(crate) approved_commands: HashSet<Vec<String>>,
+    /// Where persistent approvals are appended; set by
+    /// [`SessionState::load_approved_commands`].
+    approved_commands_path: Option<PathBuf>,
+    /// [`command_id`]s already in the approvals file.
+    persisted_command_ids: HashSet<String>,
+    /// Most recently executed commands, newest first, without duplicates.
+    pub(crate) recent_commands: Vec<Vec<String>>,
     pub(crate) history: ConversationHistory,
     pub(crate) token_info: Option<TokenUsageInfo>,
+    /// Per-turn usage as reported, oldest first, capped at
+    /// `token_usage_timeline_len` entries.
+    token_usage_timeline: VecDeque<(Instant, TokenUsage)>,
+    token_usage_timeline_len: usize,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    /// Set while a rate-limit window is exhausted: when it is expected to reset.
+    pub(crate) rate_limited_until: Option<Instant>,
 }
 
 impl SessionState {
@@ -23,6 +88,7 @@ impl SessionState {
     pub(crate) fn new() -> Self {
         Self {
             history: ConversationHistory::new(),
+            token_usage_timeline_len: DEFAULT_TOKEN_USAGE_TIMELINE_LEN,
             ..Default::default()
         }
     }
@@ -49,10 +115,81 @@ impl SessionState {
         self.approved_commands.insert(cmd);
     }
 
+    /// Approve `cmd` for `scope`. Persistent approvals are appended to the
+    /// file given to [`SessionState::load_approved_commands`], once per
+    /// command; without one they only last for the session.
+    pub(crate) fn approve_command(
+        &mut self,
+        cmd: Vec<String>,
+        scope: ApprovalScope,
+    ) -> std::io::Result<()> {
+        if scope == ApprovalScope::Persistent
+            && let Some(path) = self.approved_commands_path.as_ref()
+        {
+            let id = command_id(&cmd);
+            if !self.persisted_command_ids.contains(&id) {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                let line = serde_json::to_string(&PersistedApproval {
+                    id: id.clone(),
+                    command: cmd.clone(),
+                })?;
+                let mut file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)?;
+                writeln!(file, "{line}")?;
+                self.persisted_command_ids.insert(id);
+            }
+        }
+        self.add_approved_command(cmd);
+        Ok(())
+    }
+
+    /// Approve every command saved in `path` and append future persistent
+    /// approvals there. A missing file is not an error; unreadable lines are
+    /// skipped.
+    pub(crate) fn load_approved_commands(&mut self, path: &Path) -> std::io::Result<()> {
+        self.approved_commands_path = Some(path.to_path_buf());
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str::<PersistedApproval>(line) {
+                Ok(approval) => {
+                    self.persisted_command_ids.insert(approval.id);
+                    self.approved_commands.insert(approval.command);
+                }
+                Err(err) => {
+                    tracing::warn!("skipping invalid approval in {}: {err}", path.display());
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn approved_commands_ref(&self) -> &HashSet<Vec<String>> {
         &self.approved_commands
     }
 
+    // Recent command helpers
+    /// Note that `cmd` just ran: it moves to the front of the ring, and the
+    /// oldest entry drops off once [`MAX_RECENT_COMMANDS`] is exceeded.
+    pub(crate) fn record_recent_command(&mut self, cmd: Vec<String>) {
+        self.recent_commands.retain(|existing| existing != &cmd);
+        self.recent_commands.insert(0, cmd);
+        self.recent_commands.truncate(MAX_RECENT_COMMANDS);
+    }
+
+    /// Recently executed commands, newest first.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn recent_commands(&self) -> &[Vec<String>] {
+        &self.recent_commands
+    }
+
     // Token/rate limit helpers
     pub(crate) fn update_token_info_from_usage(
         &mut self,
@@ -64,17 +201,367 @@ impl SessionState {
             &Some(usage.clone()),
             model_context_window,
         );
+        self.push_token_usage(Instant::now(), usage.clone());
+    }
+
+    fn push_token_usage(&mut self, at: Instant, usage: TokenUsage) {
+        if self.token_usage_timeline_len == 0 {
+            return;
+        }
+        if self.token_usage_timeline.len() == self.token_usage_timeline_len {
+            self.token_usage_timeline.pop_front();
+        }
+        self.token_usage_timeline.push_back((at, usage));
+    }
+
+    /// Usage reported for each turn with when it arrived, oldest first. Only
+    /// the most recent entries are kept; see
+    /// [`SessionState::set_token_usage_timeline_len`].
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn token_usage_timeline(&self) -> &VecDeque<(Instant, TokenUsage)> {
+        &self.token_usage_timeline
+    }
+
+    /// Keep at most `len` timeline entries, dropping the oldest beyond that.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn set_token_usage_timeline_len(&mut self, len: usize) {
+        self.token_usage_timeline_len = len;
+        while self.token_usage_timeline.len() > len {
+            self.token_usage_timeline.pop_front();
+        }
     }
 
     pub(crate) fn set_rate_limits(&mut self, snapshot: RateLimitSnapshot) {
+        self.set_rate_limits_at(snapshot, Instant::now());
+    }
+
+    /// Record `snapshot` as observed at `now`. An exhausted window blocks
+    /// requests until its reset; a snapshot with headroom clears the block.
+    fn set_rate_limits_at(&mut self, snapshot: RateLimitSnapshot, now: Instant) {
+        self.rate_limited_until = [snapshot.primary.as_ref(), snapshot.secondary.as_ref()]
+            .into_iter()
+            .flatten()
+            .filter(|window| window.used_percent >= 100.0)
+            .filter_map(|window| window.resets_in_seconds)
+            .max()
+            .map(|secs| now + Duration::from_secs(secs));
         self.latest_rate_limits = Some(snapshot);
     }
 
+    /// How long to hold off before the exhausted rate-limit window resets, or
+    /// `None` when requests can be sent now.
+    pub(crate) fn time_until_sendable(&self) -> Option<Duration> {
+        self.time_until_sendable_at(Instant::now())
+    }
+
+    fn time_until_sendable_at(&self, now: Instant) -> Option<Duration> {
+        self.rate_limited_until
+            .and_then(|until| until.checked_duration_since(now))
+            .filter(|wait| !wait.is_zero())
+    }
+
+    /// Whether a model request can be sent now, i.e. no rate-limit window is
+    /// exhausted.
+    pub(crate) fn can_send_now(&self) -> bool {
+        self.can_send_now_at(Instant::now())
+    }
+
+    fn can_send_now_at(&self, now: Instant) -> bool {
+        self.time_until_sendable_at(now).is_none()
+    }
+
     pub(crate) fn token_info_and_rate_limits(
         &self,
     ) -> (Option<TokenUsageInfo>, Option<RateLimitSnapshot>) {
         (self.token_info.clone(), self.latest_rate_limits.clone())
     }
 
+    /// Serialize token usage, context utilization and rate limits into a
+    /// stable JSON shape suitable for external dashboards.
+    ///
+    /// Keys are always present; unknown values are `null`:
+    /// - `token_usage.total` / `token_usage.last`: `input_tokens`,
+    ///   `cached_input_tokens`, `output_tokens`, `reasoning_output_tokens`,
+    ///   `total_tokens`.
+    /// - `context_window`: `size`, `tokens_in_context`, `percent_remaining`.
+    /// - `rate_limits.primary` / `rate_limits.secondary`: `used_percent`,
+    ///   `window_minutes`, `resets_in_seconds`.
+    pub(crate) fn usage_json(&self) -> Value {
+        let token_usage = match &self.token_info {
+            Some(info) => json!({
+                "total": token_usage_json(&info.total_token_usage),
+                "last": token_usage_json(&info.last_token_usage),
+            }),
+            None => json!({ "total": null, "last": null }),
+        };
+
+        let context_size = self
+            .token_info
+            .as_ref()
+            .and_then(|info| info.model_context_window);
+        let tokens_in_context = self
+            .token_info
+            .as_ref()
+            .map(|info| info.last_token_usage.tokens_in_context_window());
+        let percent_remaining = self.token_info.as_ref().and_then(|info| {
+            info.model_context_window.map(|window| {
+                info.last_token_usage
+                    .percent_of_context_window_remaining(window)
+            })
+        });
+
+        let rate_limits = self.latest_rate_limits.as_ref();
+        json!({
+            "token_usage": token_usage,
+            "context_window": {
+                "size": context_size,
+                "tokens_in_context": tokens_in_context,
+                "percent_remaining": percent_remaining,
+            },
+            "rate_limits": {
+                "primary": rate_limit_window_json(rate_limits.and_then(|r| r.primary.as_ref())),
+                "secondary": rate_limit_window_json(rate_limits.and_then(|r| r.secondary.as_ref())),
+            },
+        })
+    }
+
     // Pending input/approval moved to TurnState.
 }
+
+fn token_usage_json(usage: &TokenUsage) -> Value {
+    json!({
+        "input_tokens": usage.input_tokens,
+        "cached_input_tokens": usage.cached_input_tokens,
+        "output_tokens": usage.output_tokens,
+        "reasoning_output_tokens": usage.reasoning_output_tokens,
+        "total_tokens": usage.total_tokens,
+    })
+}
+
+fn rate_limit_window_json(window: Option<&RateLimitWindow>) -> Value {
+    match window {
+        Some(window) => json!({
+            "used_percent": window.used_percent,
+            "window_minutes": window.window_minutes,
+            "resets_in_seconds": window.resets_in_seconds,
+        }),
+        None => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn usage(input: u64, output: u64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: 0,
+            output_tokens: output,
+            reasoning_output_tokens: 0,
+            total_tokens: input + output,
+        }
+    }
+
+    #[test]
+    fn token_usage_timeline_keeps_the_latest_turns_alongside_the_total() {
+        let mut state = SessionState::new();
+        state.set_token_usage_timeline_len(2);
+        state.update_token_info_from_usage(&usage(100, 20), Some(100_000));
+        state.update_token_info_from_usage(&usage(300, 40), Some(100_000));
+        state.update_token_info_from_usage(&usage(500, 60), Some(100_000));
+
+        let timeline: Vec<u64> = state
+            .token_usage_timeline()
+            .iter()
+            .map(|(_, usage)| usage.total_tokens)
+            .collect();
+        assert_eq!(timeline, vec![340, 560]);
+        let (_, first) = &state.token_usage_timeline()[0];
+        let (_, second) = &state.token_usage_timeline()[1];
+        assert_eq!(first.input_tokens, 300);
+        assert_eq!(second.input_tokens, 500);
+
+        let total = state
+            .token_info
+            .as_ref()
+            .map(|info| info.total_token_usage.total_tokens);
+        assert_eq!(total, Some(1_020));
+    }
+
+    #[test]
+    fn usage_json_reports_tokens_context_and_rate_limits() {
+        let mut state = SessionState::new();
+        state.update_token_info_from_usage(&usage(100, 20), Some(100_000));
+        state.update_token_info_from_usage(&usage(300, 40), Some(100_000));
+        state.set_rate_limits(RateLimitSnapshot {
+            primary: Some(RateLimitWindow {
+                used_percent: 42.5,
+                window_minutes: Some(300),
+                resets_in_seconds: Some(60),
+            }),
+            secondary: None,
+        });
+
+        let value = state.usage_json();
+
+        assert_eq!(value["token_usage"]["total"]["input_tokens"], json!(400));
+        assert_eq!(value["token_usage"]["total"]["output_tokens"], json!(60));
+        assert_eq!(value["token_usage"]["last"]["total_tokens"], json!(340));
+        assert_eq!(value["context_window"]["size"], json!(100_000));
+        assert_eq!(value["context_window"]["tokens_in_context"], json!(340));
+        assert_eq!(value["context_window"]["percent_remaining"], json!(100));
+        assert_eq!(
+            value["rate_limits"]["primary"],
+            json!({
+                "used_percent": 42.5,
+                "window_minutes": 300,
+                "resets_in_seconds": 60,
+            })
+        );
+        assert_eq!(value["rate_limits"]["secondary"], Value::Null);
+    }
+
+    fn window(used_percent: f64, resets_in_seconds: u64) -> RateLimitWindow {
+        RateLimitWindow {
+            used_percent,
+            window_minutes: Some(300),
+            resets_in_seconds: Some(resets_in_seconds),
+        }
+    }
+
+    #[test]
+    fn exhausted_rate_limit_blocks_until_reset() {
+        let mut state = SessionState::new();
+        let now = Instant::now();
+        state.set_rate_limits_at(
+            RateLimitSnapshot {
+                primary: Some(window(100.0, 60)),
+                secondary: Some(window(30.0, 3_600)),
+            },
+            now,
+        );
+
+        assert_eq!(
+            state.time_until_sendable_at(now),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            state.time_until_sendable_at(now + Duration::from_secs(59)),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            state.time_until_sendable_at(now + Duration::from_secs(60)),
+            None
+        );
+
+        // A fresh snapshot with headroom lifts the block immediately.
+        state.set_rate_limits_at(
+            RateLimitSnapshot {
+                primary: Some(window(10.0, 60)),
+                secondary: None,
+            },
+            now,
+        );
+        assert_eq!(state.time_until_sendable_at(now), None);
+        assert_eq!(state.time_until_sendable(), None);
+    }
+
+    #[test]
+    fn can_send_now_waits_for_the_latest_exhausted_window() {
+        let mut state = SessionState::new();
+        let now = Instant::now();
+        assert!(state.can_send_now_at(now));
+
+        state.set_rate_limits_at(
+            RateLimitSnapshot {
+                primary: Some(window(100.0, 60)),
+                secondary: Some(window(100.0, 600)),
+            },
+            now,
+        );
+        assert!(!state.can_send_now_at(now));
+        assert!(!state.can_send_now_at(now + Duration::from_secs(60)));
+        assert!(state.can_send_now_at(now + Duration::from_secs(600)));
+    }
+
+    #[test]
+    fn persistent_approvals_survive_a_new_session_but_session_ones_do_not() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = approved_commands_file(dir.path(), Path::new("/work/project"));
+        let cmd = |s: &str| vec!["bash".to_string(), "-lc".to_string(), s.to_string()];
+
+        let mut state = SessionState::new();
+        state.load_approved_commands(&path).expect("load");
+        state
+            .approve_command(cmd("cargo test"), ApprovalScope::Persistent)
+            .expect("approve");
+        state
+            .approve_command(cmd("cargo test"), ApprovalScope::Persistent)
+            .expect("approve");
+        state
+            .approve_command(cmd("rm -rf target"), ApprovalScope::Session)
+            .expect("approve");
+        assert!(
+            state
+                .approved_commands_ref()
+                .contains(&cmd("rm -rf target"))
+        );
+
+        let saved = std::fs::read_to_string(&path).expect("read approvals");
+        assert_eq!(saved.lines().count(), 1);
+
+        let mut next = SessionState::new();
+        next.load_approved_commands(&path).expect("reload");
+        assert_eq!(
+            next.approved_commands_ref(),
+            &HashSet::from([cmd("cargo test")])
+        );
+    }
+
+    #[test]
+    fn recent_commands_move_repeats_to_front_and_stay_bounded() {
+        let cmd = |s: &str| vec!["bash".to_string(), "-lc".to_string(), s.to_string()];
+        let mut state = SessionState::new();
+        state.record_recent_command(cmd("ls"));
+        state.record_recent_command(cmd("cargo test"));
+        state.record_recent_command(cmd("git status"));
+        state.record_recent_command(cmd("ls"));
+
+        assert_eq!(
+            state.recent_commands(),
+            &[cmd("ls"), cmd("git status"), cmd("cargo test")]
+        );
+
+        for i in 0..MAX_RECENT_COMMANDS {
+            state.record_recent_command(cmd(&format!("echo {i}")));
+        }
+        assert_eq!(state.recent_commands().len(), MAX_RECENT_COMMANDS);
+        assert_eq!(
+            state.recent_commands()[0],
+            cmd(&format!("echo {}", MAX_RECENT_COMMANDS - 1))
+        );
+        assert!(!state.recent_commands().contains(&cmd("ls")));
+    }
+
+    #[test]
+    fn usage_json_uses_nulls_before_any_usage() {
+        let state = SessionState::new();
+
+        let value = state.usage_json();
+
+        assert_eq!(
+            value,
+            json!({
+                "token_usage": { "total": null, "last": null },
+                "context_window": {
+                    "size": null,
+                    "tokens_in_context": null,
+                    "percent_remaining": null,
+                },
+                "rate_limits": { "primary": null, "secondary": null },
+            })
+        );
+    }
+}
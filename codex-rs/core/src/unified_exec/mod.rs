@@ -1,3 +1,4 @@
+use futures::future::join_all;
 use portable_pty::CommandBuilder;
 use portable_pty::PtySize;
 use portable_pty::native_pty_system;
@@ -16,6 +17,7 @@ use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
 use tokio::time::Instant;
+use tracing::warn;
 
 use crate::exec_command::ExecCommandSession;
 use crate::truncate::truncate_middle;
@@ -27,6 +29,9 @@ pub(crate) use errors::UnifiedExecError;
 const DEFAULT_TIMEOUT_MS: u64 = 1_000;
 const MAX_TIMEOUT_MS: u64 = 60_000;
 const UNIFIED_EXEC_OUTPUT_MAX_BYTES: usize = 128 * 1024; // 128 KiB
+/// How often [`UnifiedExecSessionManager::terminate_all`] checks whether a
+/// killed session has exited.
+const KILL_POLL_INTERVAL: Duration = Duration::from_millis(20);
 
 #[derive(Debug)]
 pub(crate) struct UnifiedExecRequest<'a> {
@@ -41,6 +46,17 @@ pub(crate) struct UnifiedExecResult {
     pub output: String,
 }
 
+/// A session as listed by [`UnifiedExecSessionManager::session_summaries`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct UnifiedExecSessionSummary {
+    pub session_id: i32,
+    pub command: Vec<String>,
+    /// Time since the session was started.
+    pub age: Duration,
+    /// The process has exited, but the session has not been polled since.
+    pub exited: bool,
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct UnifiedExecSessionManager {
     next_session_id: AtomicI32,
@@ -50,6 +66,8 @@ pub(crate) struct UnifiedExecSessionManager {
 #[derive(Debug)]
 struct ManagedUnifiedExecSession {
     session: ExecCommandSession,
+    command: Vec<String>,
+    started_at: Instant,
     output_buffer: OutputBuffer,
     /// Notifies waiters whenever new output has been appended to
     /// `output_buffer`, allowing clients to poll for fresh data.
@@ -102,6 +120,7 @@ type OutputHandles = (OutputBuffer, Arc<Notify>);
 impl ManagedUnifiedExecSession {
     fn new(
         session: ExecCommandSession,
+        command: Vec<String>,
         initial_output_rx: tokio::sync::broadcast::Receiver<Vec<u8>>,
     ) -> Self {
         let output_buffer = Arc::new(Mutex::new(OutputBufferState::default()));
@@ -120,6 +139,8 @@ impl ManagedUnifiedExecSession {
 
         Self {
             session,
+            command,
+            started_at: Instant::now(),
             output_buffer,
             output_notify,
             output_task,
@@ -149,6 +170,61 @@ impl Drop for ManagedUnifiedExecSession {
 }
 
 impl UnifiedExecSessionManager {
+    /// Whether any interactive session is still alive. Sessions whose process
+    /// has exited but that have not been polled since are not counted.
+    pub async fn has_running_session(&self) -> bool {
+        self.sessions
+            .lock()
+            .await
+            .values()
+            .any(|session| !session.has_exited())
+    }
+
+    /// Every session the manager holds, ordered by id, for diagnosing a stuck
+    /// session.
+    pub async fn session_summaries(&self) -> Vec<UnifiedExecSessionSummary> {
+        let mut summaries: Vec<UnifiedExecSessionSummary> = self
+            .sessions
+            .lock()
+            .await
+            .iter()
+            .map(|(session_id, managed)| UnifiedExecSessionSummary {
+                session_id: *session_id,
+                command: managed.command.clone(),
+                age: managed.started_at.elapsed(),
+                exited: managed.has_exited(),
+            })
+            .collect();
+        summaries.sort_unstable_by_key(|summary| summary.session_id);
+        summaries
+    }
+
+    /// Kill every session concurrently and wait up to `timeout` for them to
+    /// exit. Sessions are dropped either way so a stuck PTY cannot hold up
+    /// shutdown; the ids of those still alive at the deadline are returned.
+    pub async fn terminate_all(&self, timeout: Duration) -> Vec<i32> {
+        let sessions: Vec<(i32, ManagedUnifiedExecSession)> =
+            self.sessions.lock().await.drain().collect();
+        let deadline = Instant::now() + timeout;
+        let waits = sessions.iter().map(|(session_id, managed)| async move {
+            if !managed.has_exited()
+                && let Err(err) = managed.session.kill()
+            {
+                warn!("failed to kill unified exec session {session_id}: {err}");
+            }
+            while !managed.has_exited() {
+                if Instant::now() >= deadline {
+                    return Some(*session_id);
+                }
+                tokio::time::sleep(KILL_POLL_INTERVAL).await;
+            }
+            None
+        });
+        let mut stuck: Vec<i32> = join_all(waits).await.into_iter().flatten().collect();
+        stuck.sort_unstable();
+        stuck
+    }
+
     pub async fn handle_request(
         &self,
         request: UnifiedExecRequest<'_>,
@@ -197,7 +273,8 @@ impl UnifiedExecSessionManager {
             let command = request.input_chunks.to_vec();
             let new_id = self.next_session_id.fetch_add(1, Ordering::SeqCst);
             let (session, initial_output_rx) = create_unified_exec_session(&command).await?;
-            let managed_session = ManagedUnifiedExecSession::new(session, initial_output_rx);
+            let managed_session =
+                ManagedUnifiedExecSession::new(session, command, initial_output_rx);
             let (buffer, notify) = managed_session.output_handles();
             writer_tx = managed_session.writer_sender();
             output_buffer = buffer;
@@ -606,6 +683,101 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn has_running_session_tracks_open_shells() -> Result<(), UnifiedExecError> {
+        skip_if_sandbox!(Ok(()));
+
+        let manager = UnifiedExecSessionManager::default();
+        assert!(!manager.has_running_session().await);
+
+        let open_shell = manager
+            .handle_request(UnifiedExecRequest {
+                session_id: None,
+                input_chunks: &["/bin/bash".to_string(), "-i".to_string()],
+                timeout_ms: Some(2_500),
+            })
+            .await?;
+        let session_id = open_shell.session_id.expect("expected session id");
+        assert!(manager.has_running_session().await);
+
+        manager
+            .handle_request(UnifiedExecRequest {
+                session_id: Some(session_id),
+                input_chunks: &["exit\n".to_string()],
+                timeout_ms: Some(2_500),
+            })
+            .await?;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        assert!(!manager.has_running_session().await);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn session_summaries_list_every_open_shell() -> Result<(), UnifiedExecError> {
+        skip_if_sandbox!(Ok(()));
+
+        let manager = UnifiedExecSessionManager::default();
+        assert!(manager.session_summaries().await.is_empty());
+
+        let command = vec!["/bin/bash".to_string(), "-i".to_string()];
+        let mut session_ids = Vec::new();
+        for _ in 0..2 {
+            let result = manager
+                .handle_request(UnifiedExecRequest {
+                    session_id: None,
+                    input_chunks: &command,
+                    timeout_ms: Some(1_000),
+                })
+                .await?;
+            session_ids.push(result.session_id.expect("expected session id"));
+        }
+
+        let summaries = manager.session_summaries().await;
+        assert_eq!(
+            summaries
+                .iter()
+                .map(|summary| summary.session_id)
+                .collect::<Vec<_>>(),
+            session_ids
+        );
+        for summary in &summaries {
+            assert_eq!(summary.command, command);
+            assert!(!summary.exited);
+        }
+
+        manager.terminate_all(Duration::from_secs(5)).await;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn terminate_all_kills_and_drops_every_session() -> Result<(), UnifiedExecError> {
+        skip_if_sandbox!(Ok(()));
+
+        let manager = UnifiedExecSessionManager::default();
+        for _ in 0..2 {
+            manager
+                .handle_request(UnifiedExecRequest {
+                    session_id: None,
+                    input_chunks: &["/bin/bash".to_string(), "-i".to_string()],
+                    timeout_ms: Some(1_000),
+                })
+                .await?;
+        }
+        assert_eq!(manager.sessions.lock().await.len(), 2);
+
+        let stuck = manager.terminate_all(Duration::from_secs(5)).await;
+        assert!(stuck.is_empty(), "sessions did not exit: {stuck:?}");
+        assert!(manager.sessions.lock().await.is_empty());
+        assert!(!manager.has_running_session().await);
+
+        Ok(())
+    }
+
     #[cfg(unix)]
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn reusing_completed_session_returns_unknown_session() -> Result<(), UnifiedExecError> {
@@ -1217,6 +1217,11 @@ pub enum ReviewDecision {
     /// remainder of the session.
     ApprovedForSession,
 
+    /// User has approved this command and wants identical instances approved
+    /// automatically in this session and in later sessions started in the
+    /// same working directory.
+    ApprovedAlways,
+
     /// User has denied this command and the agent should not execute it, but
     /// it should continue the session and try something else.
     #[default]
@@ -57,6 +57,9 @@ pub(crate) struct App {
     // Pager overlay state (Transcript or Static like Diff)
     pub(crate) overlay: Option<Overlay>,
     pub(crate) deferred_history_lines: Vec<Line<'static>>,
+    /// Cells inserted while the transcript overlay is open, flushed to it in
+    /// one batch before it handles the next event.
+    pub(crate) pending_transcript_cells: Vec<Arc<dyn HistoryCell>>,
     has_emitted_history_lines: bool,
 
     pub(crate) enhanced_keys_supported: bool,
@@ -141,6 +144,7 @@ impl App {
             transcript_cells: Vec::new(),
             overlay: None,
             deferred_history_lines: Vec::new(),
+            pending_transcript_cells: Vec::new(),
             has_emitted_history_lines: false,
             commit_anim_running: Arc::new(AtomicBool::new(false)),
             backtrack: BacktrackState::default(),
@@ -172,6 +176,7 @@ impl App {
         event: TuiEvent,
     ) -> Result<bool> {
         if self.overlay.is_some() {
+            self.flush_pending_transcript_cells();
             let _ = self.handle_backtrack_overlay_event(tui, event).await?;
         } else {
             match event {
@@ -186,6 +191,7 @@ impl App {
                     let pasted = pasted.replace("\r", "\n");
                     self.chat_widget.handle_paste(pasted);
                 }
+                TuiEvent::Mouse(_) => {}
                 TuiEvent::Draw => {
                     self.chat_widget.maybe_post_pending_notification(tui);
                     if self
@@ -226,8 +232,8 @@ impl App {
             }
             AppEvent::InsertHistoryCell(cell) => {
                 let cell: Arc<dyn HistoryCell> = cell.into();
-                if let Some(Overlay::Transcript(t)) = &mut self.overlay {
-                    t.insert_cell(cell.clone());
+                if let Some(Overlay::Transcript(_)) = &self.overlay {
+                    self.pending_transcript_cells.push(cell.clone());
                     tui.frame_requester().schedule_frame();
                 }
                 self.transcript_cells.push(cell.clone());
@@ -250,6 +256,13 @@ impl App {
                     }
                 }
             }
+            AppEvent::PrefillComposer(text) => {
+                if let Some(Overlay::Transcript(_)) = &self.overlay {
+                    self.close_transcript_overlay(tui);
+                }
+                self.chat_widget.set_composer_text(text);
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::StartCommitAnimation => {
                 if self
                     .commit_anim_running
@@ -287,15 +300,19 @@ impl App {
                 self.chat_widget.on_diff_complete();
                 // Enter alternate screen using TUI helper and build pager lines
                 let _ = tui.enter_alt_screen();
+                tui.set_mouse_capture(self.config.tui_pager_mouse_capture);
                 let pager_lines: Vec<ratatui::text::Line<'static>> = if text.trim().is_empty() {
                     vec!["No changes detected.".italic().into()]
                 } else {
                     text.lines().map(ansi_escape_line).collect()
                 };
-                self.overlay = Some(Overlay::new_static_with_title(
-                    pager_lines,
-                    "D I F F".to_string(),
-                ));
+                self.overlay = Some(
+                    Overlay::new_static_with_title(pager_lines, "D I F F".to_string())
+                        .with_page_overlap(self.config.tui_pager_page_overlap)
+                        .with_wheel_lines(self.config.tui_pager_wheel_lines)
+                        .with_max_wrap_width(self.config.tui_pager_max_wrap_width)
+                        .with_snapshot_key(self.config.tui_pager_snapshot_key.as_deref()),
+                );
                 tui.frame_requester().schedule_frame();
             }
             AppEvent::StartFileSearch(query) => {
@@ -371,6 +388,15 @@ impl App {
         self.chat_widget.token_usage()
     }
 
+    /// Hand cells buffered since the last event to the transcript overlay so a
+    /// burst of streamed cells is wrapped once rather than per cell.
+    fn flush_pending_transcript_cells(&mut self) {
+        let cells = std::mem::take(&mut self.pending_transcript_cells);
+        if let Some(Overlay::Transcript(t)) = &mut self.overlay {
+            t.insert_cells(cells);
+        }
+    }
+
     fn on_update_reasoning_effort(&mut self, effort: Option<ReasoningEffortConfig>) {
         self.chat_widget.set_reasoning_effort(effort);
         self.config.model_reasoning_effort = effort;
@@ -386,7 +412,19 @@ impl App {
             } => {
                 // Enter alternate screen and set viewport to full size.
                 let _ = tui.enter_alt_screen();
-                self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
+                tui.set_mouse_capture(self.config.tui_pager_mouse_capture);
+                self.overlay = Some(
+                    Overlay::new_transcript(self.transcript_cells.clone())
+                        .with_page_overlap(self.config.tui_pager_page_overlap)
+                        .with_wheel_lines(self.config.tui_pager_wheel_lines)
+                        .with_max_wrap_width(self.config.tui_pager_max_wrap_width)
+                        .with_snapshot_key(self.config.tui_pager_snapshot_key.as_deref())
+                        .with_transcript_follow(self.config.tui_transcript_follow)
+                        .with_max_cells(self.config.tui_transcript_max_cells)
+                        .with_max_line_chars(self.config.tui_transcript_max_line_chars)
+                        .with_highlights(&self.config.tui_transcript_highlights)
+                        .with_app_event_tx(self.app_event_tx.clone()),
+                );
                 tui.frame_requester().schedule_frame();
             }
             // Esc primes/advances backtracking only in normal (not working) mode
@@ -479,6 +517,7 @@ mod tests {
             transcript_cells: Vec::new(),
             overlay: None,
             deferred_history_lines: Vec::new(),
+            pending_transcript_cells: Vec::new(),
             has_emitted_history_lines: false,
             enhanced_keys_supported: false,
             commit_anim_running: Arc::new(AtomicBool::new(false)),
@@ -505,6 +544,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reopened_transcript_does_not_repeat_cells_queued_before_close() {
+        let mut app = make_test_app();
+        app.overlay = Some(Overlay::new_transcript(app.transcript_cells.clone()));
+
+        // A cell arrives while the overlay is open but is not flushed before
+        // the overlay closes.
+        let cell: Arc<dyn HistoryCell> =
+            Arc::new(AgentMessageCell::new(vec![Line::from("late reply")], true));
+        app.pending_transcript_cells.push(cell.clone());
+        app.transcript_cells.push(cell);
+        app.drop_transcript_overlay();
+
+        app.overlay = Some(Overlay::new_transcript(app.transcript_cells.clone()));
+        app.flush_pending_transcript_cells();
+
+        let Some(Overlay::Transcript(transcript)) = &app.overlay else {
+            panic!("transcript overlay");
+        };
+        assert_eq!(
+            transcript.export_markdown().matches("late reply").count(),
+            1
+        );
+    }
+
     #[test]
     fn backtrack_selection_with_duplicate_history_targets_unique_turn() {
         let mut app = make_test_app();
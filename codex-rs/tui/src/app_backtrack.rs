@@ -40,6 +40,11 @@ impl App {
         tui: &mut tui::Tui,
         event: TuiEvent,
     ) -> Result<bool> {
+        if self.overlay.as_ref().is_some_and(Overlay::captures_keys) {
+            // Typing into the overlay (e.g. its search prompt); Esc cancels there.
+            self.overlay_forward_event(tui, event)?;
+            return Ok(true);
+        }
         if self.backtrack.overlay_preview_active {
             match event {
                 TuiEvent::Key(KeyEvent {
@@ -110,19 +115,32 @@ impl App {
     /// Open transcript overlay (enters alternate screen and shows full transcript).
     pub(crate) fn open_transcript_overlay(&mut self, tui: &mut tui::Tui) {
         let _ = tui.enter_alt_screen();
-        self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
+        tui.set_mouse_capture(self.config.tui_pager_mouse_capture);
+        self.overlay = Some(
+            Overlay::new_transcript(self.transcript_cells.clone())
+                .with_page_overlap(self.config.tui_pager_page_overlap)
+                .with_wheel_lines(self.config.tui_pager_wheel_lines)
+                .with_max_wrap_width(self.config.tui_pager_max_wrap_width)
+                .with_snapshot_key(self.config.tui_pager_snapshot_key.as_deref())
+                .with_transcript_follow(self.config.tui_transcript_follow)
+                .with_max_cells(self.config.tui_transcript_max_cells)
+                .with_max_line_chars(self.config.tui_transcript_max_line_chars)
+                .with_highlights(&self.config.tui_transcript_highlights)
+                .with_app_event_tx(self.app_event_tx.clone()),
+        );
         tui.frame_requester().schedule_frame();
     }
 
     /// Close transcript overlay and restore normal UI.
     pub(crate) fn close_transcript_overlay(&mut self, tui: &mut tui::Tui) {
+        tui.set_mouse_capture(false);
         let _ = tui.leave_alt_screen();
         let was_backtrack = self.backtrack.overlay_preview_active;
         if !self.deferred_history_lines.is_empty() {
             let lines = std::mem::take(&mut self.deferred_history_lines);
             tui.insert_history_lines(lines);
         }
-        self.overlay = None;
+        self.drop_transcript_overlay();
         self.backtrack.overlay_preview_active = false;
         if was_backtrack {
             // Ensure backtrack state is fully reset when overlay closes (e.g. via 'q').
@@ -130,6 +148,13 @@ impl App {
         }
     }
 
+    /// Drop the overlay along with cells still queued for it. They are already
+    /// in `transcript_cells`, so a reopened overlay would show them twice.
+    pub(crate) fn drop_transcript_overlay(&mut self) {
+        self.overlay = None;
+        self.pending_transcript_cells.clear();
+    }
+
     /// Re-render the full transcript into the terminal scrollback in one call.
     /// Useful when switching sessions to ensure prior history remains visible.
     pub(crate) fn render_transcript_once(&mut self, tui: &mut tui::Tui) {
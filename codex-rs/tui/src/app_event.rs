@@ -43,6 +43,10 @@ pub(crate) enum AppEvent {
 
     InsertHistoryCell(Box<dyn HistoryCell>),
 
+    /// Replace the composer text with a command picked in the transcript,
+    /// e.g. a failed command to tweak and resubmit.
+    PrefillComposer(String),
+
     StartCommitAnimation,
     StopCommitAnimation,
     CommitTick,
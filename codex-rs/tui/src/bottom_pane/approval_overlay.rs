@@ -354,6 +354,12 @@ fn exec_options() -> Vec<ApprovalOption> {
             decision: ReviewDecision::ApprovedForSession,
             shortcut: Some('a'),
         },
+        ApprovalOption {
+            label: "Always approve in project".to_string(),
+            description: "(P) Also approve in future sessions here".to_string(),
+            decision: ReviewDecision::ApprovedAlways,
+            shortcut: Some('p'),
+        },
         ApprovalOption {
             label: "Cancel".to_string(),
             description: "(N) Do not run the command".to_string(),
@@ -413,6 +419,19 @@ fn build_exec_history_lines(
                 ],
             )
         }
+        ApprovedAlways => {
+            let snippet = Span::from(exec_snippet(&command)).dim();
+            (
+                "✔ ".green(),
+                vec![
+                    "You ".into(),
+                    "approved".bold(),
+                    " codex to run ".into(),
+                    snippet,
+                    " every time in this project".bold(),
+                ],
+            )
+        }
         Denied => {
             let snippet = Span::from(exec_snippet(&command)).dim();
             (
@@ -504,6 +523,23 @@ mod tests {
         assert!(saw_op, "expected approval decision to emit an op");
     }
 
+    #[test]
+    fn project_shortcut_approves_always() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(make_exec_request(), tx);
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::ExecApproval { decision: d, .. }) = ev {
+                decision = Some(d);
+                break;
+            }
+        }
+        assert_eq!(decision, Some(ReviewDecision::ApprovedAlways));
+    }
+
     #[test]
     fn header_includes_command_snippet() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use codex_core::config::Config;
 use codex_core::config_types::Notifications;
@@ -73,6 +74,7 @@ use crate::clipboard_paste::paste_image_to_temp_png;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
 use crate::exec_cell::ExecCell;
+use crate::exec_cell::ExecCompletion;
 use crate::exec_cell::new_active_exec_command;
 use crate::get_git_diff::get_git_diff;
 use crate::history_cell;
@@ -118,6 +120,7 @@ const MAX_TRACKED_GHOST_COMMITS: usize = 20;
 struct RunningCommand {
     command: Vec<String>,
     parsed_cmd: Vec<ParsedCommand>,
+    cwd: PathBuf,
 }
 
 const RATE_LIMIT_WARNING_THRESHOLDS: [f64; 3] = [75.0, 90.0, 95.0];
@@ -671,9 +674,9 @@ impl ChatWidget {
 
     pub(crate) fn handle_exec_end_now(&mut self, ev: ExecCommandEndEvent) {
         let running = self.running_commands.remove(&ev.call_id);
-        let (command, parsed) = match running {
-            Some(rc) => (rc.command, rc.parsed_cmd),
-            None => (vec![ev.call_id.clone()], Vec::new()),
+        let (command, parsed, cwd) = match running {
+            Some(rc) => (rc.command, rc.parsed_cmd, Some(rc.cwd)),
+            None => (vec![ev.call_id.clone()], Vec::new(), None),
         };
 
         let needs_new = self
@@ -683,25 +686,26 @@ impl ChatWidget {
             .unwrap_or(true);
         if needs_new {
             self.flush_active_cell();
-            self.active_cell = Some(Box::new(new_active_exec_command(
-                ev.call_id.clone(),
-                command,
-                parsed,
-            )));
+            let mut cell = new_active_exec_command(ev.call_id.clone(), command, parsed);
+            cell.cwd = cwd;
+            cell.trim_trailing_blank_lines = self.config.tui_trim_trailing_blank_lines;
+            self.active_cell = Some(Box::new(cell));
         }
 
+        let mut completion = None;
         if let Some(cell) = self
             .active_cell
             .as_mut()
             .and_then(|c| c.as_any_mut().downcast_mut::<ExecCell>())
         {
-            cell.complete_call(
+            completion = cell.complete_call(
                 &ev.call_id,
                 CommandOutput {
                     exit_code: ev.exit_code,
                     stdout: ev.stdout.clone(),
                     stderr: ev.stderr.clone(),
                     formatted_output: ev.formatted_output.clone(),
+                    diagnostics: Vec::new(),
                 },
                 ev.duration,
             );
@@ -709,6 +713,23 @@ impl ChatWidget {
                 self.flush_active_cell();
             }
         }
+        if let Some(completion) = completion {
+            self.notify_exec_completion(completion);
+        }
+    }
+
+    /// Quick commands finish before anyone looks away, so only long-running
+    /// ones are worth a notification.
+    fn notify_exec_completion(&mut self, completion: ExecCompletion) {
+        if completion.duration < EXEC_NOTIFICATION_MIN_DURATION {
+            return;
+        }
+        let command = shlex::try_join(completion.command.iter().map(String::as_str))
+            .unwrap_or_else(|_| completion.command.join(" "));
+        self.notify(Notification::ExecCommandComplete {
+            command,
+            exit_code: completion.exit_code,
+        });
     }
 
     pub(crate) fn handle_patch_apply_end_now(
@@ -771,6 +792,7 @@ impl ChatWidget {
             RunningCommand {
                 command: ev.command.clone(),
                 parsed_cmd: ev.parsed_cmd.clone(),
+                cwd: ev.cwd.clone(),
             },
         );
         if let Some(cell) = self
@@ -787,11 +809,11 @@ impl ChatWidget {
         } else {
             self.flush_active_cell();
 
-            self.active_cell = Some(Box::new(new_active_exec_command(
-                ev.call_id.clone(),
-                ev.command.clone(),
-                ev.parsed_cmd,
-            )));
+            let mut cell =
+                new_active_exec_command(ev.call_id.clone(), ev.command.clone(), ev.parsed_cmd);
+            cell.cwd = Some(ev.cwd);
+            cell.trim_trailing_blank_lines = self.config.tui_trim_trailing_blank_lines;
+            self.active_cell = Some(Box::new(cell));
         }
 
         self.request_redraw();
@@ -2007,6 +2029,7 @@ enum Notification {
     AgentTurnComplete { response: String },
     ExecApprovalRequested { command: String },
     EditApprovalRequested { cwd: PathBuf, changes: Vec<PathBuf> },
+    ExecCommandComplete { command: String, exit_code: i32 },
 }
 
 impl Notification {
@@ -2030,6 +2053,14 @@ impl Notification {
                     }
                 )
             }
+            Notification::ExecCommandComplete { command, exit_code } => {
+                let command = truncate_text(command, 30);
+                if *exit_code == 0 {
+                    format!("Command finished: {command}")
+                } else {
+                    format!("Command failed (exit {exit_code}): {command}")
+                }
+            }
         }
     }
 
@@ -2038,6 +2069,7 @@ impl Notification {
             Notification::AgentTurnComplete { .. } => "agent-turn-complete",
             Notification::ExecApprovalRequested { .. }
             | Notification::EditApprovalRequested { .. } => "approval-requested",
+            Notification::ExecCommandComplete { .. } => "exec-complete",
         }
     }
 
@@ -2066,6 +2098,7 @@ impl Notification {
 }
 
 const AGENT_NOTIFICATION_PREVIEW_GRAPHEMES: usize = 200;
+const EXEC_NOTIFICATION_MIN_DURATION: Duration = Duration::from_secs(30);
 
 const EXAMPLE_PROMPTS: [&str; 6] = [
     "Explain this codebase",
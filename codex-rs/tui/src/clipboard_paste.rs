@@ -142,6 +142,22 @@ pub fn paste_image_to_temp_png() -> Result<(PathBuf, PastedImageInfo), PasteImag
     ))
 }
 
+/// Place `text` on the system clipboard.
+#[cfg(not(target_os = "android"))]
+pub fn copy_text_to_clipboard(text: &str) -> Result<(), PasteImageError> {
+    let mut cb = arboard::Clipboard::new()
+        .map_err(|e| PasteImageError::ClipboardUnavailable(e.to_string()))?;
+    cb.set_text(text.to_string())
+        .map_err(|e| PasteImageError::ClipboardUnavailable(e.to_string()))
+}
+
+#[cfg(target_os = "android")]
+pub fn copy_text_to_clipboard(_text: &str) -> Result<(), PasteImageError> {
+    Err(PasteImageError::ClipboardUnavailable(
+        "clipboard copy is unsupported on Android".into(),
+    ))
+}
+
 /// Normalize pasted text that may represent a filesystem path.
 ///
 /// Supports:
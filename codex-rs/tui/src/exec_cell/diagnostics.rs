@@ -0,0 +1,139 @@
+#![expect(clippy::expect_used)]
+
+//! Extraction of compiler/linter diagnostics (`file:line:col` plus severity)
+//! from exec output.
+
+use regex_lite::Regex;
+
+lazy_static::lazy_static! {
+    /// rustc header line, e.g. `error[E0425]: cannot find value` or
+    /// `warning: unused variable`.
+    static ref RUSTC_HEADER: Regex =
+        Regex::new(r"^(error|warning)(?:\[\w+\])?: (.+)$").expect("failed to compile rustc regex");
+    /// rustc location line following a header, e.g. `  --> src/main.rs:2:5`.
+    static ref RUSTC_LOCATION: Regex =
+        Regex::new(r"^\s*--> (.+):(\d+):(\d+)$").expect("failed to compile rustc regex");
+    /// gcc/clang style, e.g. `main.c:3:5: error: expected ';'`. The column is
+    /// optional.
+    static ref GCC_LINE: Regex = Regex::new(
+        r"^(.+?):(\d+):(?:(\d+):)? (?:fatal )?(error|warning|note): (.+)$"
+    ).expect("failed to compile gcc regex");
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "error" => Some(Severity::Error),
+            "warning" => Some(Severity::Warning),
+            "note" => Some(Severity::Note),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Diagnostic {
+    pub(crate) file: String,
+    pub(crate) line: u32,
+    pub(crate) column: Option<u32>,
+    pub(crate) severity: Severity,
+    pub(crate) message: String,
+    /// 0-based line of the parsed output where the diagnostic starts.
+    pub(crate) output_line: usize,
+}
+
+impl Diagnostic {
+    /// `file:line`, plus `:column` when known.
+    pub(crate) fn location(&self) -> String {
+        match self.column {
+            Some(column) => format!("{}:{}:{column}", self.file, self.line),
+            None => format!("{}:{}", self.file, self.line),
+        }
+    }
+}
+
+/// A diagnostic output format. Add an implementation to [`PARSERS`] to
+/// recognize another tool.
+pub(crate) trait DiagnosticParser: Sync {
+    fn parse(&self, output: &str) -> Vec<Diagnostic>;
+}
+
+/// rustc/cargo: a severity header followed by a `-->` location line.
+struct RustcParser;
+
+impl DiagnosticParser for RustcParser {
+    fn parse(&self, output: &str) -> Vec<Diagnostic> {
+        let mut out = Vec::new();
+        let mut pending: Option<(Severity, String, usize)> = None;
+        for (idx, line) in output.lines().enumerate() {
+            if let Some(caps) = RUSTC_HEADER.captures(line) {
+                pending =
+                    Severity::parse(&caps[1]).map(|severity| (severity, caps[2].to_string(), idx));
+            } else if let Some(caps) = RUSTC_LOCATION.captures(line)
+                && let Some((severity, message, output_line)) = pending.take()
+                && let Ok(line) = caps[2].parse()
+            {
+                out.push(Diagnostic {
+                    file: caps[1].to_string(),
+                    line,
+                    column: caps[3].parse().ok(),
+                    severity,
+                    message,
+                    output_line,
+                });
+            }
+        }
+        out
+    }
+}
+
+/// gcc/clang and the many tools that mimic them: `file:line[:col]: severity: message`.
+struct GccParser;
+
+impl DiagnosticParser for GccParser {
+    fn parse(&self, output: &str) -> Vec<Diagnostic> {
+        output
+            .lines()
+            .enumerate()
+            .filter_map(|(output_line, line)| {
+                let caps = GCC_LINE.captures(line)?;
+                Some(Diagnostic {
+                    file: caps[1].to_string(),
+                    line: caps[2].parse().ok()?,
+                    column: caps.get(3).and_then(|m| m.as_str().parse().ok()),
+                    severity: Severity::parse(&caps[4])?,
+                    message: caps[5].to_string(),
+                    output_line,
+                })
+            })
+            .collect()
+    }
+}
+
+pub(crate) static PARSERS: &[&dyn DiagnosticParser] = &[&RustcParser, &GccParser];
+
+/// Run every known parser over `output`, ordered by where each diagnostic
+/// starts.
+pub(crate) fn parse_diagnostics(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = PARSERS
+        .iter()
+        .flat_map(|parser| parser.parse(output))
+        .collect();
+    diagnostics.sort_by_key(|d| d.output_line);
+    diagnostics
+}
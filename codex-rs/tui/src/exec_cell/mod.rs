@@ -1,12 +1,23 @@
+mod diagnostics;
 mod model;
 mod render;
+mod search_hits;
 
+pub(crate) use diagnostics::Diagnostic;
+#[cfg(test)]
+pub(crate) use diagnostics::Severity;
+pub(crate) use diagnostics::parse_diagnostics;
 pub(crate) use model::CommandOutput;
 #[cfg(test)]
+pub(crate) use model::EXEC_JSON_OUTPUT_MAX_BYTES;
+#[cfg(test)]
 pub(crate) use model::ExecCall;
 pub(crate) use model::ExecCell;
+pub(crate) use model::ExecCompletion;
 pub(crate) use render::OutputLinesParams;
 pub(crate) use render::TOOL_CALL_MAX_LINES;
 pub(crate) use render::new_active_exec_command;
 pub(crate) use render::output_lines;
 pub(crate) use render::spinner;
+pub(crate) use search_hits::SearchHit;
+pub(crate) use search_hits::parse_search_hits;
@@ -1,7 +1,15 @@
+use std::path::PathBuf;
 use std::time::Duration;
 use std::time::Instant;
 
+use super::diagnostics::Diagnostic;
+use super::diagnostics::parse_diagnostics;
 use codex_protocol::parse_command::ParsedCommand;
+use serde_json::Value;
+use serde_json::json;
+
+/// Per-stream cap on output included in [`ExecCell::to_json`].
+pub(crate) const EXEC_JSON_OUTPUT_MAX_BYTES: usize = 64 * 1024;
 
 #[derive(Clone, Debug)]
 pub(crate) struct CommandOutput {
@@ -9,6 +17,18 @@ pub(crate) struct CommandOutput {
     pub(crate) stdout: String,
     pub(crate) stderr: String,
     pub(crate) formatted_output: String,
+    /// Diagnostics found in stdout and stderr, parsed once by
+    /// [`ExecCell::complete_call`] so rendering does not rerun the parsers.
+    pub(crate) diagnostics: Vec<Diagnostic>,
+}
+
+/// Summary of a call that just finished, returned by
+/// [`ExecCell::complete_call`] so the UI can react (e.g. notify the user).
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ExecCompletion {
+    pub(crate) command: Vec<String>,
+    pub(crate) exit_code: i32,
+    pub(crate) duration: Duration,
 }
 
 #[derive(Debug, Clone)]
@@ -19,16 +39,26 @@ pub(crate) struct ExecCall {
     pub(crate) output: Option<CommandOutput>,
     pub(crate) start_time: Option<Instant>,
     pub(crate) duration: Option<Duration>,
+    /// How many identical consecutive exploring calls this entry stands for.
+    pub(crate) repeat_count: usize,
 }
 
 #[derive(Debug)]
 pub(crate) struct ExecCell {
     pub(crate) calls: Vec<ExecCall>,
+    /// Working directory the cell's commands ran in, when known.
+    pub(crate) cwd: Option<PathBuf>,
+    /// Collapse trailing blank lines in displayed output to a single one.
+    pub(crate) trim_trailing_blank_lines: bool,
 }
 
 impl ExecCell {
     pub(crate) fn new(call: ExecCall) -> Self {
-        Self { calls: vec![call] }
+        Self {
+            calls: vec![call],
+            cwd: None,
+            trim_trailing_blank_lines: true,
+        }
     }
 
     pub(crate) fn with_added_call(
@@ -44,10 +74,16 @@ impl ExecCell {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            repeat_count: 1,
         };
         if self.is_exploring_cell() && Self::is_exploring_call(&call) {
+            // Repeats are collapsed by `complete_call` once the output is known.
+            let mut calls = self.calls.clone();
+            calls.push(call);
             Some(Self {
-                calls: [self.calls.clone(), vec![call]].concat(),
+                calls,
+                cwd: self.cwd.clone(),
+                trim_trailing_blank_lines: self.trim_trailing_blank_lines,
             })
         } else {
             None
@@ -59,12 +95,53 @@ impl ExecCell {
         call_id: &str,
         output: CommandOutput,
         duration: Duration,
-    ) {
-        if let Some(call) = self.calls.iter_mut().rev().find(|c| c.call_id == call_id) {
-            call.output = Some(output);
-            call.duration = Some(duration);
-            call.start_time = None;
+    ) -> Option<ExecCompletion> {
+        let idx = self.calls.iter().rposition(|c| c.call_id == call_id)?;
+        let call = &mut self.calls[idx];
+        let exit_code = output.exit_code;
+        let mut output = output;
+        output.diagnostics = parse_diagnostics(&output.stdout);
+        output.diagnostics.extend(parse_diagnostics(&output.stderr));
+        call.output = Some(output);
+        call.duration = Some(duration);
+        call.start_time = None;
+        let completion = ExecCompletion {
+            command: call.command.clone(),
+            exit_code,
+            duration,
+        };
+        self.collapse_repeat(idx);
+        Some(completion)
+    }
+
+    /// Fold the finished call at `idx` into the call before it when both are
+    /// the same exploring command with the same output, bumping that call's
+    /// repeat count. Calls whose output differs stay separate.
+    fn collapse_repeat(&mut self, idx: usize) {
+        let Some(prev_idx) = idx.checked_sub(1) else {
+            return;
+        };
+        let (prev, call) = (&self.calls[prev_idx], &self.calls[idx]);
+        let same_output = match (&prev.output, &call.output) {
+            (Some(a), Some(b)) => {
+                a.exit_code == b.exit_code
+                    && a.stdout == b.stdout
+                    && a.stderr == b.stderr
+                    && a.formatted_output == b.formatted_output
+            }
+            _ => false,
+        };
+        if !same_output
+            || !Self::is_exploring_call(prev)
+            || prev.command != call.command
+            || prev.parsed != call.parsed
+        {
+            return;
         }
+        let call = self.calls.remove(idx);
+        let prev = &mut self.calls[prev_idx];
+        prev.repeat_count += call.repeat_count;
+        prev.duration = call.duration;
     }
 
     pub(crate) fn should_flush(&self) -> bool {
@@ -85,6 +162,7 @@ impl ExecCell {
                     stdout: String::new(),
                     stderr: String::new(),
                     formatted_output: String::new(),
+                    diagnostics: Vec::new(),
                 });
             }
         }
@@ -109,6 +187,40 @@ impl ExecCell {
         self.calls.iter()
     }
 
+    /// Machine-readable record of the cell's calls, one object per call.
+    /// stdout/stderr are capped at [`EXEC_JSON_OUTPUT_MAX_BYTES`] each, with
+    /// `truncated` set when either was cut.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn to_json(&self) -> Value {
+        let calls = self
+            .calls
+            .iter()
+            .map(|call| {
+                let (stdout, stdout_cut) = call
+                    .output
+                    .as_ref()
+                    .map(|o| truncate_output(&o.stdout))
+                    .unwrap_or_default();
+                let (stderr, stderr_cut) = call
+                    .output
+                    .as_ref()
+                    .map(|o| truncate_output(&o.stderr))
+                    .unwrap_or_default();
+                json!({
+                    "call_id": call.call_id,
+                    "command": call.command,
+                    "exit_code": call.output.as_ref().map(|o| o.exit_code),
+                    "duration_ms": call.duration.map(|d| d.as_millis() as u64),
+                    "stdout": stdout,
+                    "stderr": stderr,
+                    "truncated": stdout_cut || stderr_cut,
+                    "termination": if call.output.is_some() { "exited" } else { "running" },
+                })
+            })
+            .collect();
+        Value::Array(calls)
+    }
+
     pub(super) fn is_exploring_call(call: &ExecCall) -> bool {
         !call.parsed.is_empty()
             && call.parsed.iter().all(|p| {
@@ -121,3 +233,15 @@ impl ExecCell {
             })
     }
 }
+
+/// Cut `text` to at most [`EXEC_JSON_OUTPUT_MAX_BYTES`] on a char boundary.
+fn truncate_output(text: &str) -> (String, bool) {
+    if text.len() <= EXEC_JSON_OUTPUT_MAX_BYTES {
+        return (text.to_string(), false);
+    }
+    let mut end = EXEC_JSON_OUTPUT_MAX_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    (text[..end].to_string(), true)
+}
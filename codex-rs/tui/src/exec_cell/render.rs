@@ -1,9 +1,11 @@
 use std::time::Instant;
 
+use super::diagnostics::Severity;
 use super::model::CommandOutput;
 use super::model::ExecCall;
 use super::model::ExecCell;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::history_cell::CellId;
 use crate::history_cell::HistoryCell;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::line_utils::prefix_lines;
@@ -29,6 +31,15 @@ pub(crate) struct OutputLinesParams {
     pub(crate) only_err: bool,
     pub(crate) include_angle_pipe: bool,
     pub(crate) include_prefix: bool,
+    /// Collapse trailing blank lines to a single one.
+    pub(crate) trim_trailing_blank_lines: bool,
+}
+
+/// Backtick fence one longer than the longest backtick run in `text` (and at
+/// least three), so output containing its own fences cannot close the block.
+fn markdown_fence(text: &str) -> String {
+    let longest_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat((longest_run + 1).max(3))
 }
 
 pub(crate) fn new_active_exec_command(
@@ -43,6 +54,7 @@ pub(crate) fn new_active_exec_command(
         output: None,
         start_time: Some(Instant::now()),
         duration: None,
+        repeat_count: 1,
     })
 }
 
@@ -54,6 +66,7 @@ pub(crate) fn output_lines(
         only_err,
         include_angle_pipe,
         include_prefix,
+        trim_trailing_blank_lines,
     } = params;
     let CommandOutput {
         exit_code,
@@ -67,7 +80,17 @@ pub(crate) fn output_lines(
     };
 
     let src = if *exit_code == 0 { stdout } else { stderr };
-    let lines: Vec<&str> = src.lines().collect();
+    let mut lines: Vec<&str> = src.lines().collect();
+    if trim_trailing_blank_lines {
+        // Extra trailing blank lines only waste rows; one still separates
+        // output that deliberately ends in a blank.
+        while lines.len() >= 2
+            && lines[lines.len() - 1].trim().is_empty()
+            && lines[lines.len() - 2].trim().is_empty()
+        {
+            lines.pop();
+        }
+    }
     let total = lines.len();
     let limit = TOOL_CALL_MAX_LINES;
 
@@ -160,12 +183,71 @@ impl HistoryCell for ExecCell {
                     ])
                 };
                 result.push_span(format!(" • {duration}").dim());
+                if let Some(summary) = diagnostics_summary(output) {
+                    result.push_span(format!(" • {summary}").dim());
+                }
                 lines.push(result);
             }
             lines.push("".into());
         }
         lines
     }
+
+    fn transcript_markdown(&self) -> String {
+        let text = self.command_with_output_text();
+        let fence = markdown_fence(&text);
+        format!("{fence}console\n{text}{fence}")
+    }
+
+    /// The first call's id; later calls only join an existing cell.
+    fn id(&self) -> Option<CellId> {
+        self.iter_calls()
+            .next()
+            .map(|call| CellId(call.call_id.clone()))
+    }
+}
+
+impl ExecCell {
+    /// Each call as a `$ command` line followed by its output and exit code,
+    /// ready to paste into a code fence in a bug report.
+    pub(crate) fn command_with_output_text(&self) -> String {
+        let mut out = String::new();
+        for call in self.iter_calls() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("$ {}\n", strip_bash_lc_and_escape(&call.command)));
+            if let Some(output) = call.output.as_ref() {
+                let text = output.formatted_output.trim_end_matches('\n');
+                if !text.is_empty() {
+                    out.push_str(text);
+                    out.push('\n');
+                }
+                out.push_str(&format!("[exit code: {}]\n", output.exit_code));
+            }
+        }
+        out
+    }
+
+    /// Every call's command and complete, untruncated output, for viewing in
+    /// a dedicated pager.
+    pub(crate) fn full_output_lines(&self) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        for call in self.iter_calls() {
+            if !lines.is_empty() {
+                lines.push("".into());
+            }
+            lines.push(Line::from(vec![
+                "$ ".dim(),
+                strip_bash_lc_and_escape(&call.command).bold(),
+            ]));
+            if let Some(output) = call.output.as_ref() {
+                lines.extend(output.formatted_output.lines().map(ansi_escape_line));
+                lines.push(format!("[exit code: {}]", output.exit_code).dim().into());
+            }
+        }
+        lines
+    }
 }
 
 impl WidgetRef for &ExecCell {
@@ -214,16 +296,19 @@ impl ExecCell {
         let mut out_indented = Vec::new();
         while !calls.is_empty() {
             let mut call = calls.remove(0);
-            if call
-                .parsed
-                .iter()
-                .all(|parsed| matches!(parsed, ParsedCommand::Read { .. }))
+            // Repeated calls keep their own line so the count stays attached.
+            if call.repeat_count == 1
+                && call
+                    .parsed
+                    .iter()
+                    .all(|parsed| matches!(parsed, ParsedCommand::Read { .. }))
             {
                 while let Some(next) = calls.first() {
-                    if next
-                        .parsed
-                        .iter()
-                        .all(|parsed| matches!(parsed, ParsedCommand::Read { .. }))
+                    if next.repeat_count == 1
+                        && next
+                            .parsed
+                            .iter()
+                            .all(|parsed| matches!(parsed, ParsedCommand::Read { .. }))
                     {
                         call.parsed.extend(next.parsed.clone());
                         calls.remove(0);
@@ -238,7 +323,7 @@ impl ExecCell {
                 .iter()
                 .all(|parsed| matches!(parsed, ParsedCommand::Read { .. }));
 
-            let call_lines: Vec<(&str, Vec<Span<'static>>)> = if reads_only {
+            let mut call_lines: Vec<(&str, Vec<Span<'static>>)> = if reads_only {
                 let names = call
                     .parsed
                     .iter()
@@ -278,6 +363,11 @@ impl ExecCell {
                 }
                 lines
             };
+            if call.repeat_count > 1
+                && let Some((_, spans)) = call_lines.last_mut()
+            {
+                spans.push(format!(" (×{})", call.repeat_count).dim());
+            }
 
             for (title, line) in call_lines {
                 let line = Line::from(line);
@@ -364,6 +454,7 @@ impl ExecCell {
                     only_err: false,
                     include_angle_pipe: false,
                     include_prefix: false,
+                    trim_trailing_blank_lines: self.trim_trailing_blank_lines,
                 },
             );
             let trimmed_output =
@@ -490,3 +581,33 @@ const EXEC_DISPLAY_LAYOUT: ExecDisplayLayout = ExecDisplayLayout::new(
     PrefixedBlock::new("  └ ", "    "),
     5,
 );
+
+/// "2 errors, 1 warning, first at src/main.rs:3:5" for the diagnostics found
+/// in `output`, if any. The location is the first error's, or the first
+/// warning's when there are no errors.
+fn diagnostics_summary(output: &CommandOutput) -> Option<String> {
+    let diagnostics = &output.diagnostics;
+    let count = |severity| {
+        diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    };
+    let plural = |n: usize, noun: &str| format!("{n} {noun}{}", if n == 1 { "" } else { "s" });
+    let parts: Vec<String> = [
+        (count(Severity::Error), "error"),
+        (count(Severity::Warning), "warning"),
+    ]
+    .into_iter()
+    .filter(|(n, _)| *n > 0)
+    .map(|(n, noun)| plural(n, noun))
+    .collect();
+    let first = [Severity::Error, Severity::Warning]
+        .into_iter()
+        .find_map(|severity| diagnostics.iter().find(|d| d.severity == severity))?;
+    Some(format!(
+        "{}, first at {}",
+        parts.join(", "),
+        first.location()
+    ))
+}
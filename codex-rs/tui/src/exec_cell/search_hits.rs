@@ -0,0 +1,55 @@
+#![expect(clippy::expect_used)]
+
+//! Extraction of `path:line:col:match` hits as printed by `rg --vimgrep`
+//! (and `grep -n --column`), for jumping between them one by one.
+
+use std::path::PathBuf;
+
+use regex_lite::Regex;
+
+lazy_static::lazy_static! {
+    /// `src/main.rs:12:5:    let x = foo();`. Both line and column are
+    /// required, which keeps plain `path:line` mentions out.
+    static ref VIMGREP_LINE: Regex =
+        Regex::new(r"^(.+?):(\d+):(\d+):(.*)$").expect("failed to compile vimgrep regex");
+    /// The tail of a gcc-style diagnostic, which shares the vimgrep prefix and
+    /// is handled by the diagnostics parser instead.
+    static ref DIAGNOSTIC_TAIL: Regex =
+        Regex::new(r"^ (?:fatal )?(?:error|warning|note): ").expect("failed to compile vimgrep regex");
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SearchHit {
+    pub(crate) path: PathBuf,
+    pub(crate) line: u32,
+    pub(crate) column: u32,
+    /// 0-based line of the parsed output holding the hit.
+    pub(crate) output_line: usize,
+}
+
+impl SearchHit {
+    /// `path:line:col`, as editors accept it on the command line.
+    pub(crate) fn location(&self) -> String {
+        format!("{}:{}:{}", self.path.display(), self.line, self.column)
+    }
+}
+
+/// Every vimgrep-style hit in `output`, in order.
+pub(crate) fn parse_search_hits(output: &str) -> Vec<SearchHit> {
+    output
+        .lines()
+        .enumerate()
+        .filter_map(|(output_line, line)| {
+            let caps = VIMGREP_LINE.captures(line)?;
+            if DIAGNOSTIC_TAIL.is_match(&caps[4]) {
+                return None;
+            }
+            Some(SearchHit {
+                path: PathBuf::from(&caps[1]),
+                line: caps[2].parse().ok()?,
+                column: caps[3].parse().ok()?,
+                output_line,
+            })
+        })
+        .collect()
+}
@@ -3,6 +3,9 @@ use std::path::PathBuf;
 
 use dirs::home_dir;
 use shlex::try_join;
+use unicode_width::UnicodeWidthStr;
+
+use crate::text_formatting::center_truncate_path;
 
 pub(crate) fn escape_command(command: &[String]) -> String {
     try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
@@ -33,6 +36,31 @@ where
     Some(rel.to_path_buf())
 }
 
+/// `directory` for display: home-relative paths start with `~`, and paths
+/// wider than `max_width` are middle-elided (`~/a/…/z`).
+pub(crate) fn format_directory_display(directory: &Path, max_width: Option<usize>) -> String {
+    let formatted = if let Some(rel) = relativize_to_home(directory) {
+        if rel.as_os_str().is_empty() {
+            "~".to_string()
+        } else {
+            format!("~{}{}", std::path::MAIN_SEPARATOR, rel.display())
+        }
+    } else {
+        directory.display().to_string()
+    };
+
+    if let Some(max_width) = max_width {
+        if max_width == 0 {
+            return String::new();
+        }
+        if UnicodeWidthStr::width(formatted.as_str()) > max_width {
+            return center_truncate_path(&formatted, max_width);
+        }
+    }
+
+    formatted
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -50,4 +78,22 @@ mod tests {
         let cmdline = strip_bash_lc_and_escape(&args);
         assert_eq!(cmdline, "echo hello");
     }
+
+    #[test]
+    fn format_directory_display_elides_deep_home_paths() {
+        let mut dir = home_dir().expect("home directory");
+        for part in ["projects", "deeply", "nested", "work", "tree", "leaf"] {
+            dir.push(part);
+        }
+        let sep = std::path::MAIN_SEPARATOR;
+
+        let formatted = format_directory_display(&dir, Some(12));
+        assert_eq!(formatted, format!("~{sep}…{sep}leaf"));
+
+        let full = format_directory_display(&dir, None);
+        assert_eq!(
+            full,
+            format!("~{sep}projects{sep}deeply{sep}nested{sep}work{sep}tree{sep}leaf")
+        );
+    }
 }
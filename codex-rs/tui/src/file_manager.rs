@@ -0,0 +1,25 @@
+//! Open a directory in the platform's file manager.
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+use std::process::Stdio;
+
+/// Spawn Finder (macOS), Explorer (Windows) or `xdg-open` (elsewhere) on
+/// `path` without waiting for it to exit.
+pub(crate) fn open_in_file_manager(path: &Path) -> io::Result<()> {
+    let program = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "explorer"
+    } else {
+        "xdg-open"
+    };
+    Command::new(program)
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
@@ -4,7 +4,7 @@ use crate::exec_cell::OutputLinesParams;
 use crate::exec_cell::TOOL_CALL_MAX_LINES;
 use crate::exec_cell::output_lines;
 use crate::exec_cell::spinner;
-use crate::exec_command::relativize_to_home;
+use crate::exec_command::format_directory_display;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::markdown::append_markdown;
 use crate::render::line_utils::line_to_static;
@@ -55,6 +55,11 @@ pub(crate) enum PatchEventType {
     ApplyBegin { auto_approved: bool },
 }
 
+/// Identifies a history cell independently of its position, which shifts as
+/// earlier cells are dropped or filtered out.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct CellId(pub(crate) String);
+
 /// Represents an event to display in the conversation history. Returns its
 /// `Vec<Line<'static>>` representation to make it easier to display in a
 /// scrollable list.
@@ -65,6 +70,12 @@ pub(crate) trait HistoryCell: std::fmt::Debug + Send + Sync + Any {
         self.display_lines(u16::MAX)
     }
 
+    /// Untruncated transcript lines for cells whose `transcript_lines` are
+    /// abbreviated. `None` means `transcript_lines` is already complete.
+    fn full_transcript_lines(&self) -> Option<Vec<Line<'static>>> {
+        None
+    }
+
     fn desired_height(&self, width: u16) -> u16 {
         Paragraph::new(Text::from(self.display_lines(width)))
             .wrap(Wrap { trim: false })
@@ -76,6 +87,38 @@ pub(crate) trait HistoryCell: std::fmt::Debug + Send + Sync + Any {
     fn is_stream_continuation(&self) -> bool {
         false
     }
+
+    /// Whether this cell is part of an assistant answer, as opposed to
+    /// reasoning, tool calls or UI notices.
+    fn is_final_answer(&self) -> bool {
+        false
+    }
+
+    /// The cell as Markdown for sharing a session. Defaults to the plain text
+    /// of `transcript_lines`.
+    fn transcript_markdown(&self) -> String {
+        lines_to_plain_text(&self.transcript_lines())
+    }
+
+    /// Stable id for jumping back to this cell. `None` for cells that cannot
+    /// be told apart from others, such as UI notices.
+    fn id(&self) -> Option<CellId> {
+        None
+    }
+}
+
+/// Join the span contents of `lines`, dropping styling, one line per row.
+pub(crate) fn lines_to_plain_text(lines: &[Line<'_>]) -> String {
+    lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 impl dyn HistoryCell {
@@ -124,6 +167,21 @@ impl HistoryCell for UserHistoryCell {
         lines.extend(self.message.lines().map(|l| l.to_string().into()));
         lines
     }
+
+    fn transcript_markdown(&self) -> String {
+        let quoted: Vec<String> = self
+            .message
+            .lines()
+            .map(|l| {
+                if l.is_empty() {
+                    ">".to_string()
+                } else {
+                    format!("> {l}")
+                }
+            })
+            .collect();
+        format!("**user**\n\n{}", quoted.join("\n"))
+    }
 }
 
 #[derive(Debug)]
@@ -217,9 +275,22 @@ impl HistoryCell for AgentMessageCell {
         out
     }
 
+    fn transcript_markdown(&self) -> String {
+        let text = lines_to_plain_text(&self.lines);
+        if self.is_first_line {
+            format!("**codex**\n\n{text}")
+        } else {
+            text
+        }
+    }
+
     fn is_stream_continuation(&self) -> bool {
         !self.is_first_line
     }
+
+    fn is_final_answer(&self) -> bool {
+        true
+    }
 }
 
 #[derive(Debug)]
@@ -277,6 +348,45 @@ impl HistoryCell for PatchHistoryCell {
             width as usize,
         )
     }
+
+    fn transcript_markdown(&self) -> String {
+        let mut paths: Vec<&PathBuf> = self.changes.keys().collect();
+        paths.sort();
+        let mut out = String::from("```diff\n");
+        for path in paths {
+            let name = path.strip_prefix(&self.cwd).unwrap_or(path).display();
+            match &self.changes[path] {
+                FileChange::Add { content } => {
+                    out.push_str(&format!("--- /dev/null\n+++ b/{name}\n"));
+                    content
+                        .lines()
+                        .for_each(|l| out.push_str(&format!("+{l}\n")));
+                }
+                FileChange::Delete { content } => {
+                    out.push_str(&format!("--- a/{name}\n+++ /dev/null\n"));
+                    content
+                        .lines()
+                        .for_each(|l| out.push_str(&format!("-{l}\n")));
+                }
+                FileChange::Update {
+                    unified_diff,
+                    move_path,
+                } => {
+                    let new_name = move_path
+                        .as_deref()
+                        .map(|p| p.strip_prefix(&self.cwd).unwrap_or(p).display().to_string())
+                        .unwrap_or_else(|| name.to_string());
+                    out.push_str(&format!("--- a/{name}\n+++ b/{new_name}\n"));
+                    out.push_str(unified_diff);
+                    if !unified_diff.ends_with('\n') {
+                        out.push('\n');
+                    }
+                }
+            }
+        }
+        out.push_str("```");
+        out
+    }
 }
 
 #[derive(Debug)]
@@ -477,26 +587,7 @@ impl SessionHeaderHistoryCell {
     }
 
     fn format_directory_inner(directory: &Path, max_width: Option<usize>) -> String {
-        let formatted = if let Some(rel) = relativize_to_home(directory) {
-            if rel.as_os_str().is_empty() {
-                "~".to_string()
-            } else {
-                format!("~{}{}", std::path::MAIN_SEPARATOR, rel.display())
-            }
-        } else {
-            directory.display().to_string()
-        };
-
-        if let Some(max_width) = max_width {
-            if max_width == 0 {
-                return String::new();
-            }
-            if UnicodeWidthStr::width(formatted.as_str()) > max_width {
-                return crate::text_formatting::center_truncate_path(&formatted, max_width);
-            }
-        }
-
-        formatted
+        format_directory_display(directory, max_width)
     }
 
     fn reasoning_label(&self) -> Option<&'static str> {
@@ -918,6 +1009,25 @@ pub(crate) fn new_error_event(message: String) -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
+/// Severity of a UI notice injected into the history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(test), allow(dead_code))]
+pub(crate) enum NoticeStyle {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A one-line notice formatted like the matching info/warning/error event.
+#[cfg_attr(not(test), allow(dead_code))]
+pub(crate) fn new_notice(message: String, style: NoticeStyle) -> PlainHistoryCell {
+    match style {
+        NoticeStyle::Info => new_info_event(message, None),
+        NoticeStyle::Warning => new_warning_event(message),
+        NoticeStyle::Error => new_error_event(message),
+    }
+}
+
 pub(crate) fn new_stream_error_event(message: String) -> PlainHistoryCell {
     let lines: Vec<Line<'static>> = vec![vec![padded_emoji("⚠️").into(), message.dim()].into()];
     PlainHistoryCell { lines }
@@ -1017,11 +1127,13 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
                 stdout: String::new(),
                 stderr,
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             }),
             OutputLinesParams {
                 only_err: true,
                 include_angle_pipe: true,
                 include_prefix: true,
+                trim_trailing_blank_lines: false,
             },
         ));
     }
@@ -1154,8 +1266,11 @@ fn format_mcp_invocation<'a>(invocation: McpInvocation) -> Line<'a> {
 mod tests {
     use super::*;
     use crate::exec_cell::CommandOutput;
+    use crate::exec_cell::EXEC_JSON_OUTPUT_MAX_BYTES;
     use crate::exec_cell::ExecCall;
     use crate::exec_cell::ExecCell;
+    use crate::exec_cell::ExecCompletion;
+    use crate::exec_cell::Severity;
     use codex_core::config::Config;
     use codex_core::config::ConfigOverrides;
     use codex_core::config::ConfigToml;
@@ -1447,6 +1562,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            repeat_count: 1,
         });
         // Mark call complete so markers are ✓
         cell.complete_call(
@@ -1456,6 +1572,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             },
             Duration::from_millis(1),
         );
@@ -1465,6 +1582,326 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn complete_call_reports_exit_code_and_duration() {
+        let command = vec!["bash".to_string(), "-lc".to_string(), "false".to_string()];
+        let mut cell = ExecCell::new(ExecCall {
+            call_id: "c1".to_string(),
+            command: command.clone(),
+            parsed: Vec::new(),
+            output: None,
+            start_time: Some(Instant::now()),
+            duration: None,
+            repeat_count: 1,
+        });
+
+        let completion = cell.complete_call(
+            "c1",
+            CommandOutput {
+                exit_code: 1,
+                stdout: String::new(),
+                stderr: "boom".into(),
+                formatted_output: String::new(),
+                diagnostics: Vec::new(),
+            },
+            Duration::from_secs(42),
+        );
+
+        assert_eq!(
+            completion,
+            Some(ExecCompletion {
+                command,
+                exit_code: 1,
+                duration: Duration::from_secs(42),
+            })
+        );
+        assert_eq!(
+            cell.complete_call(
+                "unknown",
+                CommandOutput {
+                    exit_code: 0,
+                    stdout: String::new(),
+                    stderr: String::new(),
+                    formatted_output: String::new(),
+                    diagnostics: Vec::new(),
+                },
+                Duration::from_secs(1),
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn exec_cell_to_json_records_each_call() {
+        let call = |id: &str, cmd: &str| ExecCall {
+            call_id: id.to_string(),
+            command: vec!["bash".into(), "-lc".into(), cmd.into()],
+            parsed: Vec::new(),
+            output: None,
+            start_time: Some(Instant::now()),
+            duration: None,
+            repeat_count: 1,
+        };
+        let mut cell = ExecCell::new(call("c1", "echo hi"));
+        cell.calls.push(call("c2", "yes"));
+        cell.complete_call(
+            "c1",
+            CommandOutput {
+                exit_code: 0,
+                stdout: "hi\n".into(),
+                stderr: String::new(),
+                formatted_output: String::new(),
+                diagnostics: Vec::new(),
+            },
+            Duration::from_millis(15),
+        );
+        cell.complete_call(
+            "c2",
+            CommandOutput {
+                exit_code: 130,
+                stdout: "y\n".repeat(EXEC_JSON_OUTPUT_MAX_BYTES),
+                stderr: "killed".into(),
+                formatted_output: String::new(),
+                diagnostics: Vec::new(),
+            },
+            Duration::from_secs(2),
+        );
+
+        let value = cell.to_json();
+        assert_eq!(
+            value[0],
+            json!({
+                "call_id": "c1",
+                "command": ["bash", "-lc", "echo hi"],
+                "exit_code": 0,
+                "duration_ms": 15,
+                "stdout": "hi\n",
+                "stderr": "",
+                "truncated": false,
+                "termination": "exited",
+            })
+        );
+        assert_eq!(value[1]["exit_code"], json!(130));
+        assert_eq!(value[1]["duration_ms"], json!(2000));
+        assert_eq!(value[1]["stderr"], json!("killed"));
+        assert_eq!(value[1]["truncated"], json!(true));
+        assert_eq!(
+            value[1]["stdout"].as_str().map(str::len),
+            Some(EXEC_JSON_OUTPUT_MAX_BYTES)
+        );
+    }
+
+    #[test]
+    fn transcript_markdown_fence_outlasts_backticks_in_output() {
+        let mut cell = ExecCell::new(ExecCall {
+            call_id: "c1".to_string(),
+            command: vec!["bash".into(), "-lc".into(), "cat README.md".into()],
+            parsed: Vec::new(),
+            output: None,
+            start_time: Some(Instant::now()),
+            duration: None,
+            repeat_count: 1,
+        });
+        cell.complete_call(
+            "c1",
+            CommandOutput {
+                exit_code: 0,
+                stdout: String::new(),
+                stderr: String::new(),
+                formatted_output: "````rust\nfn main() {}\n````\n".into(),
+                diagnostics: Vec::new(),
+            },
+            Duration::from_secs(1),
+        );
+
+        assert_eq!(
+            cell.transcript_markdown(),
+            "`````console\n$ cat README.md\n````rust\nfn main() {}\n````\n[exit code: 0]\n`````"
+        );
+    }
+
+    #[test]
+    fn command_with_output_text_includes_command_output_and_exit_code() {
+        let mut cell = ExecCell::new(ExecCall {
+            call_id: "c1".to_string(),
+            command: vec!["bash".into(), "-lc".into(), "cargo test -p foo".into()],
+            parsed: Vec::new(),
+            output: None,
+            start_time: Some(Instant::now()),
+            duration: None,
+            repeat_count: 1,
+        });
+        cell.complete_call(
+            "c1",
+            CommandOutput {
+                exit_code: 101,
+                stdout: String::new(),
+                stderr: String::new(),
+                formatted_output: "error[E0425]: cannot find value `x`\n".into(),
+                diagnostics: Vec::new(),
+            },
+            Duration::from_secs(3),
+        );
+
+        assert_eq!(
+            cell.command_with_output_text(),
+            "$ cargo test -p foo\nerror[E0425]: cannot find value `x`\n[exit code: 101]\n"
+        );
+    }
+
+    #[test]
+    fn render_output(trim_trailing_blank_lines: bool) -> Vec<String> {
+        let output = CommandOutput {
+            exit_code: 0,
+            stdout: "first\nsecond\n\n  \n\n\t\n\n".into(),
+            stderr: String::new(),
+            formatted_output: String::new(),
+            diagnostics: Vec::new(),
+        };
+        output_lines(
+            Some(&output),
+            OutputLinesParams {
+                only_err: false,
+                include_angle_pipe: false,
+                include_prefix: false,
+                trim_trailing_blank_lines,
+            },
+        )
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn output_lines_keeps_one_trailing_blank_line_when_trimming() {
+        assert_eq!(render_output(true), vec!["first", "second", ""]);
+    }
+
+    #[test]
+    fn output_lines_keeps_trailing_blank_lines_when_not_trimming() {
+        assert_eq!(
+            render_output(false),
+            vec!["first", "second", "", "  ", "", "\t", ""]
+        );
+    }
+
+    #[test]
+    fn notices_use_the_matching_event_formatting() {
+        let render = |cell: PlainHistoryCell| render_lines(&cell.display_lines(80));
+        assert_eq!(
+            render(new_notice("hi".into(), NoticeStyle::Info)),
+            render(new_info_event("hi".into(), None))
+        );
+        assert_eq!(
+            render(new_notice("careful".into(), NoticeStyle::Warning)),
+            render(new_warning_event("careful".into()))
+        );
+        assert_eq!(
+            render(new_notice("boom".into(), NoticeStyle::Error)),
+            render(new_error_event("boom".into()))
+        );
+    }
+
+    #[test]
+    fn diagnostics_parses_rustc_output() {
+        let mut cell = ExecCell::new(ExecCall {
+            call_id: "c1".to_string(),
+            command: vec!["bash".into(), "-lc".into(), "cargo build".into()],
+            parsed: Vec::new(),
+            output: None,
+            start_time: Some(Instant::now()),
+            duration: None,
+            repeat_count: 1,
+        });
+        let stderr = "\
+warning: unused variable: `y`
+ --> src/lib.rs:3:9
+  |
+3 |     let y = 1;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_y`
+
+error[E0425]: cannot find value `x` in this scope
+  --> src/main.rs:12:5
+   |
+12 |     x
+   |     ^ not found in this scope
+";
+        cell.complete_call(
+            "c1",
+            CommandOutput {
+                exit_code: 101,
+                stdout: String::new(),
+                stderr: stderr.into(),
+                formatted_output: stderr.into(),
+                diagnostics: Vec::new(),
+            },
+            Duration::from_secs(3),
+        );
+
+        let found: Vec<_> = cell.calls[0]
+            .output
+            .as_ref()
+            .expect("completed call")
+            .diagnostics
+            .iter()
+            .cloned()
+            .map(|d| (d.file, d.line, d.column, d.severity))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("src/lib.rs".to_string(), 3, Some(9), Severity::Warning),
+                ("src/main.rs".to_string(), 12, Some(5), Severity::Error),
+            ]
+        );
+
+        let result_line = render_transcript(&cell)
+            .into_iter()
+            .find(|l| l.starts_with('✗'))
+            .expect("result line");
+        assert!(
+            result_line.ends_with(" • 1 error, 1 warning, first at src/main.rs:12:5"),
+            "{result_line}"
+        );
+    }
+
+    #[test]
+    fn diagnostics_parses_gcc_output() {
+        let out = "main.c:3:5: error: expected ';' before '}' token\nutil.h:10: warning: implicit declaration\n";
+        let found: Vec<_> = crate::exec_cell::parse_diagnostics(out)
+            .into_iter()
+            .map(|d| (d.file, d.line, d.column, d.severity))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("main.c".to_string(), 3, Some(5), Severity::Error),
+                ("util.h".to_string(), 10, None, Severity::Warning),
+            ]
+        );
+    }
+
+    #[test]
+    fn search_hits_parse_ripgrep_vimgrep_output() {
+        let out = "src/main.rs:12:5:    let x = foo();\nsrc/lib.rs:3:17:pub fn foo() -> u32 {\nmain.c:3:5: error: expected ';'\nREADME.md:7: no column\n";
+        let found: Vec<_> = crate::exec_cell::parse_search_hits(out)
+            .into_iter()
+            .map(|hit| (hit.path, hit.line, hit.column, hit.output_line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (PathBuf::from("src/main.rs"), 12, 5, 0),
+                (PathBuf::from("src/lib.rs"), 3, 17, 1),
+            ]
+        );
+    }
+
     #[test]
     fn coalesces_reads_across_multiple_calls() {
         let mut cell = ExecCell::new(ExecCall {
@@ -1478,6 +1915,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            repeat_count: 1,
         });
         // Call 1: Search only
         cell.complete_call(
@@ -1487,6 +1925,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             },
             Duration::from_millis(1),
         );
@@ -1508,6 +1947,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             },
             Duration::from_millis(1),
         );
@@ -1529,6 +1969,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             },
             Duration::from_millis(1),
         );
@@ -1538,6 +1979,82 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn repeated_exploring_calls_collapse_with_count() {
+        let command = || vec!["bash".to_string(), "-lc".to_string(), "ls".to_string()];
+        let parsed = || {
+            vec![ParsedCommand::ListFiles {
+                cmd: "ls".into(),
+                path: None,
+            }]
+        };
+        let ok = || CommandOutput {
+            exit_code: 0,
+            stdout: String::new(),
+            stderr: String::new(),
+            formatted_output: String::new(),
+            diagnostics: Vec::new(),
+        };
+        let mut cell = crate::exec_cell::new_active_exec_command("c1".into(), command(), parsed());
+        // A repeat of a still-running call is kept separate so both
+        // completions find their call.
+        let pending = cell
+            .with_added_call("c2".into(), command(), parsed())
+            .unwrap();
+        assert_eq!(pending.calls.len(), 2);
+
+        cell.complete_call("c1", ok(), Duration::from_millis(1));
+        for id in ["c2", "c3"] {
+            cell = cell
+                .with_added_call(id.into(), command(), parsed())
+                .unwrap();
+            assert_eq!(cell.calls.len(), 2);
+            assert!(cell.is_active());
+            assert!(!cell.should_flush());
+            cell.complete_call(id, ok(), Duration::from_millis(1));
+            assert!(!cell.is_active());
+            assert_eq!(cell.calls.len(), 1);
+        }
+        assert_eq!(cell.calls[0].repeat_count, 3);
+
+        let rendered = render_lines(&cell.display_lines(80)).join("\n");
+        assert!(rendered.contains("List ls (×3)"), "{rendered}");
+    }
+
+    #[test]
+    fn repeated_exploring_calls_with_different_output_stay_separate() {
+        let command = || vec!["bash".to_string(), "-lc".to_string(), "ls".to_string()];
+        let parsed = || {
+            vec![ParsedCommand::ListFiles {
+                cmd: "ls".into(),
+                path: None,
+            }]
+        };
+        let listing = |files: &str| CommandOutput {
+            exit_code: 0,
+            stdout: files.into(),
+            stderr: String::new(),
+            formatted_output: files.into(),
+            diagnostics: Vec::new(),
+        };
+        let mut cell = crate::exec_cell::new_active_exec_command("c1".into(), command(), parsed());
+        cell.complete_call("c1", listing("a.txt\n"), Duration::from_millis(1));
+        cell = cell
+            .with_added_call("c2".into(), command(), parsed())
+            .unwrap();
+        cell.complete_call("c2", listing("a.txt\nb.txt\n"), Duration::from_millis(1));
+
+        assert_eq!(cell.calls.len(), 2);
+        assert!(cell.calls.iter().all(|call| call.repeat_count == 1));
+        let outputs: Vec<&str> = cell
+            .calls
+            .iter()
+            .filter_map(|call| call.output.as_ref())
+            .map(|output| output.stdout.as_str())
+            .collect();
+        assert_eq!(outputs, vec!["a.txt\n", "a.txt\nb.txt\n"]);
+    }
+
     #[test]
     fn coalesced_reads_dedupe_names() {
         let mut cell = ExecCell::new(ExecCall {
@@ -1560,6 +2077,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            repeat_count: 1,
         });
         cell.complete_call(
             "c1",
@@ -1568,6 +2086,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             },
             Duration::from_millis(1),
         );
@@ -1588,6 +2107,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            repeat_count: 1,
         });
         // Mark call complete so it renders as "Ran"
         cell.complete_call(
@@ -1597,6 +2117,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             },
             Duration::from_millis(1),
         );
@@ -1618,6 +2139,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            repeat_count: 1,
         });
         cell.complete_call(
             &call_id,
@@ -1626,6 +2148,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             },
             Duration::from_millis(1),
         );
@@ -1646,6 +2169,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            repeat_count: 1,
         });
         cell.complete_call(
             &call_id,
@@ -1654,6 +2178,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             },
             Duration::from_millis(1),
         );
@@ -1673,6 +2198,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            repeat_count: 1,
         });
         cell.complete_call(
             &call_id,
@@ -1681,6 +2207,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             },
             Duration::from_millis(1),
         );
@@ -1701,6 +2228,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            repeat_count: 1,
         });
         cell.complete_call(
             &call_id,
@@ -1709,6 +2237,7 @@ mod tests {
                 stdout: String::new(),
                 stderr: String::new(),
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             },
             Duration::from_millis(1),
         );
@@ -1729,6 +2258,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            repeat_count: 1,
         });
         let stderr: String = (1..=10)
             .map(|n| n.to_string())
@@ -1741,6 +2271,7 @@ mod tests {
                 stdout: String::new(),
                 stderr,
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             },
             Duration::from_millis(1),
         );
@@ -1775,6 +2306,7 @@ mod tests {
             output: None,
             start_time: Some(Instant::now()),
             duration: None,
+            repeat_count: 1,
         });
 
         let stderr = "error: first line on stderr\nerror: second line on stderr".to_string();
@@ -1785,6 +2317,7 @@ mod tests {
                 stdout: String::new(),
                 stderr,
                 formatted_output: String::new(),
+                diagnostics: Vec::new(),
             },
             Duration::from_millis(5),
         );
@@ -0,0 +1,277 @@
+//! Rebuild transcript cells from a saved `ResponseItem` history, for viewing
+//! a recorded session without a live conversation.
+//!
+//! Supported items:
+//! - user messages (plain text only; instructions and environment context
+//!   are skipped) become user prompt cells;
+//! - assistant messages become agent message cells;
+//! - reasoning summaries become transcript-only "thinking" cells;
+//! - `shell` function calls and local shell calls become exec cells,
+//!   completed by the matching function call output;
+//! - web searches become web search cells.
+//!
+//! Anything else (other tools, images, unknown items) renders as a dim
+//! one-line placeholder naming the item.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::ShellToolCallParams;
+use codex_protocol::models::WebSearchAction;
+use codex_protocol::parse_command::ParsedCommand;
+use codex_protocol::protocol::InputMessageKind;
+use codex_protocol::protocol::USER_MESSAGE_BEGIN;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use serde::Deserialize;
+
+use crate::exec_cell::CommandOutput;
+use crate::exec_cell::ExecCell;
+use crate::exec_cell::new_active_exec_command;
+use crate::history_cell::AgentMessageCell;
+use crate::history_cell::HistoryCell;
+use crate::history_cell::PlainHistoryCell;
+use crate::history_cell::ReasoningSummaryCell;
+use crate::history_cell::new_user_prompt;
+use crate::history_cell::new_web_search_call;
+
+/// Map `items` to history cells in order. Exec cells are emitted where the
+/// call was made and completed in place when their output arrives.
+pub(crate) fn cells_from_response_items(items: Vec<ResponseItem>) -> Vec<Arc<dyn HistoryCell>> {
+    let mut cells: Vec<Option<Arc<dyn HistoryCell>>> = Vec::new();
+    // call_id -> (slot in `cells`, cell still waiting for output)
+    let mut pending_execs: HashMap<String, (usize, ExecCell)> = HashMap::new();
+
+    for item in items {
+        match item {
+            ResponseItem::Message { role, content, .. } => {
+                if let Some(cell) = message_cell(&role, content) {
+                    cells.push(Some(cell));
+                }
+            }
+            ResponseItem::Reasoning { summary, .. } => {
+                let lines: Vec<Line<'static>> = summary
+                    .into_iter()
+                    .flat_map(|ReasoningItemReasoningSummary::SummaryText { text }| {
+                        text.lines()
+                            .map(|l| Line::from(l.to_string()))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+                if !lines.is_empty() {
+                    cells.push(Some(Arc::new(ReasoningSummaryCell::new(Vec::new(), lines))));
+                }
+            }
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => match serde_json::from_str::<ShellToolCallParams>(&arguments) {
+                Ok(params) if name == "shell" || name == "container.exec" => {
+                    let cell = exec_cell(call_id.clone(), params.command);
+                    pending_execs.insert(call_id, (cells.len(), cell));
+                    cells.push(None);
+                }
+                _ => cells.push(Some(placeholder(&format!("tool call: {name}")))),
+            },
+            ResponseItem::LocalShellCall {
+                id,
+                call_id,
+                action: LocalShellAction::Exec(action),
+                ..
+            } => {
+                let call_id = call_id.or(id).unwrap_or_default();
+                let cell = exec_cell(call_id.clone(), action.command);
+                pending_execs.insert(call_id, (cells.len(), cell));
+                cells.push(None);
+            }
+            ResponseItem::FunctionCallOutput { call_id, output } => {
+                if let Some((slot, mut cell)) = pending_execs.remove(&call_id) {
+                    cell.complete_call(&call_id, command_output(output), Duration::ZERO);
+                    cells[slot] = Some(Arc::new(cell));
+                }
+            }
+            ResponseItem::CustomToolCall { name, .. } => {
+                cells.push(Some(placeholder(&format!("tool call: {name}"))));
+            }
+            ResponseItem::CustomToolCallOutput { .. } => {}
+            ResponseItem::WebSearchCall { action, .. } => match action {
+                WebSearchAction::Search { query } => {
+                    cells.push(Some(Arc::new(new_web_search_call(query))));
+                }
+                WebSearchAction::Other => cells.push(Some(placeholder("web search"))),
+            },
+            ResponseItem::Other => cells.push(Some(placeholder("unsupported item"))),
+        }
+    }
+
+    // Calls whose output never arrived stay visible as running commands.
+    for (slot, cell) in pending_execs.into_values() {
+        cells[slot] = Some(Arc::new(cell));
+    }
+    cells.into_iter().flatten().collect()
+}
+
+fn message_cell(role: &str, content: Vec<ContentItem>) -> Option<Arc<dyn HistoryCell>> {
+    match role {
+        "user" => {
+            let text = content
+                .into_iter()
+                .filter_map(|item| match item {
+                    ContentItem::InputText { text }
+                        if matches!(
+                            InputMessageKind::from(("user", text.as_str())),
+                            InputMessageKind::Plain
+                        ) =>
+                    {
+                        Some(match text.find(USER_MESSAGE_BEGIN) {
+                            Some(idx) => text[idx + USER_MESSAGE_BEGIN.len()..].trim().to_string(),
+                            None => text,
+                        })
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            (!text.is_empty()).then(|| Arc::new(new_user_prompt(text)) as Arc<dyn HistoryCell>)
+        }
+        "assistant" => {
+            let lines: Vec<Line<'static>> = content
+                .into_iter()
+                .filter_map(|item| match item {
+                    ContentItem::OutputText { text } => Some(text),
+                    _ => None,
+                })
+                .flat_map(|text| {
+                    text.lines()
+                        .map(|l| Line::from(l.to_string()))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            (!lines.is_empty())
+                .then(|| Arc::new(AgentMessageCell::new(lines, true)) as Arc<dyn HistoryCell>)
+        }
+        _ => None,
+    }
+}
+
+fn exec_cell(call_id: String, command: Vec<String>) -> ExecCell {
+    let cmd = command.join(" ");
+    new_active_exec_command(call_id, command, vec![ParsedCommand::Unknown { cmd }])
+}
+
+/// Shell outputs are recorded as `{"output": ..., "metadata": {"exit_code": ...}}`;
+/// anything else is taken verbatim, with the exit code inferred from `success`.
+fn command_output(payload: FunctionCallOutputPayload) -> CommandOutput {
+    #[derive(Deserialize)]
+    struct Recorded {
+        output: String,
+        metadata: RecordedMetadata,
+    }
+    #[derive(Deserialize)]
+    struct RecordedMetadata {
+        exit_code: i32,
+    }
+
+    let (text, exit_code) = match serde_json::from_str::<Recorded>(&payload.content) {
+        Ok(recorded) => (recorded.output, recorded.metadata.exit_code),
+        Err(_) => {
+            let exit_code = if payload.success == Some(false) { 1 } else { 0 };
+            (payload.content, exit_code)
+        }
+    };
+    let (stdout, stderr) = if exit_code == 0 {
+        (text.clone(), String::new())
+    } else {
+        (String::new(), text.clone())
+    };
+    CommandOutput {
+        exit_code,
+        stdout,
+        stderr,
+        formatted_output: text,
+        diagnostics: Vec::new(),
+    }
+}
+
+fn placeholder(what: &str) -> Arc<dyn HistoryCell> {
+    Arc::new(PlainHistoryCell::new(vec![
+        format!("[{what} not shown]").dim().into(),
+    ]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn transcript_text(cells: &[Arc<dyn HistoryCell>]) -> Vec<String> {
+        cells
+            .iter()
+            .flat_map(|cell| cell.transcript_lines())
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn maps_messages_shell_calls_and_unknown_items() {
+        let items = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "list files".to_string(),
+                }],
+            },
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "c1".to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "c1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: r#"{"output":"a.txt\nb.txt\n","metadata":{"exit_code":0,"duration_seconds":0.1}}"#
+                        .to_string(),
+                    success: Some(true),
+                },
+            },
+            ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: "Two files.".to_string(),
+                }],
+            },
+            ResponseItem::Other,
+        ];
+
+        let cells = cells_from_response_items(items);
+        assert_eq!(cells.len(), 4);
+        let exec = cells[1]
+            .as_any()
+            .downcast_ref::<ExecCell>()
+            .expect("exec cell");
+        assert_eq!(
+            exec.command_with_output_text(),
+            "$ ls\na.txt\nb.txt\n[exit code: 0]\n"
+        );
+
+        let text = transcript_text(&cells);
+        assert!(text.contains(&"list files".to_string()), "{text:?}");
+        assert!(text.contains(&"Two files.".to_string()), "{text:?}");
+        assert_eq!(
+            text.last().map(String::as_str),
+            Some("[unsupported item not shown]")
+        );
+    }
+}
@@ -42,10 +42,12 @@ pub mod custom_terminal;
 mod diff_render;
 mod exec_cell;
 mod exec_command;
+mod file_manager;
 mod file_search;
 mod frames;
 mod get_git_diff;
 mod history_cell;
+mod history_replay;
 pub mod insert_history;
 mod key_hint;
 pub mod live_wrap;
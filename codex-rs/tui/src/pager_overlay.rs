@@ -1,17 +1,51 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::hash::Hasher;
 use std::io::Result;
+use std::path::Path;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
 
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::clipboard_paste::copy_text_to_clipboard;
+use crate::exec_cell::Diagnostic;
+use crate::exec_cell::ExecCell;
+use crate::exec_cell::SearchHit;
+use crate::exec_cell::parse_diagnostics;
+use crate::exec_cell::parse_search_hits;
+use crate::exec_command::format_directory_display;
+use crate::exec_command::strip_bash_lc_and_escape;
+use crate::file_manager::open_in_file_manager;
+use crate::history_cell::CellId;
 use crate::history_cell::HistoryCell;
+use crate::history_cell::NoticeStyle;
+use crate::history_cell::lines_to_plain_text;
+use crate::history_cell::new_info_event;
+use crate::history_cell::new_notice;
+use crate::history_replay::cells_from_response_items;
+use crate::live_wrap::take_prefix_by_width;
 use crate::render::line_utils::push_owned_lines;
+use crate::style::transcript_stripe_style;
+use crate::terminal_palette::default_bg;
 use crate::tui;
+use crate::tui::FrameRequester;
 use crate::tui::TuiEvent;
+use codex_core::config_types::TranscriptFollow;
+use codex_core::config_types::TranscriptHighlight;
+use codex_protocol::models::ResponseItem;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use crossterm::event::MouseEvent;
+use crossterm::event::MouseEventKind;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Color;
+use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::style::Styled;
 use ratatui::style::Stylize;
@@ -22,7 +56,10 @@ use ratatui::widgets::Clear;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
 use ratatui::widgets::WidgetRef;
+use regex_lite::Regex;
+use unicode_width::UnicodeWidthStr;
 
+#[allow(clippy::large_enum_variant)]
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
@@ -37,19 +74,225 @@ impl Overlay {
         Self::Static(StaticOverlay::with_title(lines, title))
     }
 
+    /// Keep `lines` rows of context from the previous page on PageUp/PageDown.
+    pub(crate) fn with_page_overlap(mut self, lines: usize) -> Self {
+        match &mut self {
+            Overlay::Transcript(o) => o.view.page_overlap = lines,
+            Overlay::Static(o) => o.view.page_overlap = lines,
+        }
+        self
+    }
+
+    /// Set how many lines one mouse wheel notch scrolls.
+    pub(crate) fn with_wheel_lines(mut self, lines: usize) -> Self {
+        match &mut self {
+            Overlay::Transcript(o) => o.view.wheel_lines = lines,
+            Overlay::Static(o) => o.view.wheel_lines = lines,
+        }
+        self
+    }
+
+    /// Cap the width lines are wrapped at, so a bogus terminal width reported
+    /// mid-resize cannot blow up the wrap cache.
+    pub(crate) fn with_max_wrap_width(mut self, width: u16) -> Self {
+        match &mut self {
+            Overlay::Transcript(o) => o.view.max_wrap_width = width,
+            Overlay::Static(o) => o.view.max_wrap_width = width,
+        }
+        self
+    }
+
+    /// Cap the number of cells the transcript retains; `None` keeps them all.
+    pub(crate) fn with_max_cells(mut self, max_cells: Option<usize>) -> Self {
+        if let Overlay::Transcript(o) = &mut self {
+            o.max_cells = max_cells;
+            o.enforce_cell_limit();
+        }
+        self
+    }
+
+    /// Truncate transcript lines longer than `chars` characters before they
+    /// are wrapped.
+    pub(crate) fn with_max_line_chars(mut self, chars: usize) -> Self {
+        if let Overlay::Transcript(o) = &mut self {
+            o.max_line_chars = Some(chars);
+            o.rebuild_texts();
+        }
+        self
+    }
+
+    /// Color every transcript match of the configured highlights. An unknown
+    /// color or invalid pattern is logged and leaves the transcript
+    /// unhighlighted.
+    pub(crate) fn with_highlights(mut self, highlights: &[TranscriptHighlight]) -> Self {
+        if highlights.is_empty() {
+            return self;
+        }
+        let rules = highlights
+            .iter()
+            .map(|highlight| {
+                highlight
+                    .color
+                    .parse::<Color>()
+                    .map(|color| (highlight.pattern.as_str(), Style::default().fg(color)))
+                    .map_err(|_| format!("unknown highlight color `{}`", highlight.color))
+            })
+            .collect::<std::result::Result<Vec<_>, String>>()
+            .and_then(compile_highlight_rules);
+        match rules {
+            Ok(rules) => {
+                if let Overlay::Transcript(o) = &mut self {
+                    o.set_highlight_rules(rules);
+                }
+            }
+            Err(err) => tracing::warn!("ignoring tui.transcript_highlights: {err}"),
+        }
+        self
+    }
+
+    /// Set how the transcript scrolls when cells are appended. No effect on
+    /// static overlays, whose content never grows.
+    pub(crate) fn with_transcript_follow(mut self, follow: TranscriptFollow) -> Self {
+        if let Overlay::Transcript(o) = &mut self {
+            o.follow = follow;
+        }
+        self
+    }
+
+    /// Let the transcript hand a failed command back to the composer with `E`.
+    pub(crate) fn with_app_event_tx(mut self, app_event_tx: AppEventSender) -> Self {
+        if let Overlay::Transcript(o) = &mut self {
+            o.app_event_tx = Some(app_event_tx);
+        }
+        self
+    }
+
+    /// Replace the key hints with `message` for `duration`, e.g. "saved to
+    /// X". Frames are scheduled to show it and to restore the hints.
+    pub(crate) fn set_transient_footer(
+        &mut self,
+        message: String,
+        duration: Duration,
+        frame_requester: &FrameRequester,
+    ) {
+        let view = self.active_view_mut();
+        view.set_transient_footer(message, duration);
+        view.schedule_transient_footer_frames(frame_requester);
+    }
+
+    /// Bind the debug snapshot to `spec` (see [`parse_key_spec`]) instead of
+    /// F12. `None` or an unrecognized spec keeps F12.
+    pub(crate) fn with_snapshot_key(mut self, spec: Option<&str>) -> Self {
+        let Some(spec) = spec else {
+            return self;
+        };
+        match parse_key_spec(spec) {
+            Some(key) => self.view_mut().snapshot_key = key,
+            None => tracing::warn!("ignoring unrecognized tui.pager_snapshot_key {spec:?}"),
+        }
+        self
+    }
+
+    fn view(&self) -> &PagerView {
+        match self {
+            Overlay::Transcript(o) => &o.view,
+            Overlay::Static(o) => &o.view,
+        }
+    }
+
+    fn view_mut(&mut self) -> &mut PagerView {
+        match self {
+            Overlay::Transcript(o) => &mut o.view,
+            Overlay::Static(o) => &mut o.view,
+        }
+    }
+
+    /// The pager on screen: the transcript's nested output view while it is
+    /// open, otherwise the overlay's own view.
+    fn active_view_mut(&mut self) -> &mut PagerView {
+        match self {
+            Overlay::Transcript(o) => match o.output_view.as_mut() {
+                Some(output_view) => &mut output_view.view,
+                None => &mut o.view,
+            },
+            Overlay::Static(o) => &mut o.view,
+        }
+    }
+
+    /// The overlay's state as plain text for bug reports: its content plus
+    /// scroll position, viewport size and wrap width.
+    pub(crate) fn debug_snapshot(&self) -> String {
+        match self {
+            Overlay::Transcript(o) => o.debug_snapshot(),
+            Overlay::Static(o) => o.debug_snapshot(),
+        }
+    }
+
+    /// Write `debug_snapshot` to a timestamped file in the temp directory and
+    /// confirm the path in the footer.
+    fn dump_debug_snapshot(&mut self, frame_requester: &FrameRequester) {
+        let message = match self.write_debug_snapshot(&std::env::temp_dir()) {
+            Ok(path) => format!("snapshot saved to {}", path.display()),
+            Err(err) => {
+                tracing::warn!("failed to write overlay snapshot: {err}");
+                "snapshot failed".to_string()
+            }
+        };
+        self.set_transient_footer(message, SNAPSHOT_FOOTER_DURATION, frame_requester);
+    }
+
+    fn write_debug_snapshot(&self, dir: &Path) -> std::io::Result<PathBuf> {
+        let path = dir.join(format!(
+            "codex-overlay-{}.txt",
+            chrono::Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+        ));
+        std::fs::write(&path, self.debug_snapshot())?;
+        Ok(path)
+    }
+
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        if let TuiEvent::Key(key_event) = &event
+            && key_event.kind == KeyEventKind::Press
+            && (key_event.code, key_event.modifiers) == self.view().snapshot_key
+        {
+            self.dump_debug_snapshot(&tui.frame_requester());
+            return Ok(());
+        }
         match self {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
         }
     }
 
+    /// Draw the overlay into a caller-provided buffer without touching the
+    /// `Tui` (no frame scheduling or replay ticks), for compositing the pager
+    /// into another layout.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn render_into(&mut self, area: Rect, buf: &mut Buffer) {
+        match self {
+            Overlay::Transcript(o) => o.render(area, buf),
+            Overlay::Static(o) => o.render(area, buf),
+        }
+    }
+
     pub(crate) fn is_done(&self) -> bool {
         match self {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
         }
     }
+
+    /// Whether the overlay is taking text input (the search prompt) or has a
+    /// nested pager open, so keys with a global meaning such as Esc must go to
+    /// it unfiltered.
+    pub(crate) fn captures_keys(&self) -> bool {
+        match self {
+            Overlay::Transcript(o) => {
+                o.output_view.is_some() || o.pending_goto.is_some() || o.view.captures_keys()
+            }
+            Overlay::Static(o) => o.view.captures_keys(),
+        }
+    }
 }
 
 // Common pager navigation hints rendered on the first line
@@ -57,6 +300,54 @@ const PAGER_KEY_HINTS: &[(&str, &str)] = &[
     ("↑/↓", "scroll"),
     ("PgUp/PgDn", "page"),
     ("Home/End", "jump"),
+    ("?", "keys"),
+];
+
+/// Bindings every pager handles, listed by the `?` help screen. The debug
+/// snapshot key is configurable, so it is appended when the screen opens.
+const PAGER_KEY_HELP: &[(&str, &str)] = &[
+    ("↑/↓", "scroll one line"),
+    ("PgUp/PgDn/Space", "scroll one page"),
+    ("Ctrl+D/Ctrl+U", "scroll half a page"),
+    ("Home", "jump to the top"),
+    ("End", "jump to the bottom and follow new output"),
+    ("Shift+End", "jump to the bottom and stay there"),
+    ("L/ScrollLock", "lock or unlock scrolling"),
+    ("←/→", "scroll clipped lines sideways"),
+    ("w", "cycle wrapping: all, long lines only, none"),
+    ("Ctrl+R", "rewrap at the current width"),
+    ("/", "search"),
+    ("n/N", "next/previous match"),
+    ("m<letter>", "set a mark"),
+    ("'<letter>", "jump to a mark"),
+    ("y", "copy the visible page"),
+    ("W", "toggle copying wrapped lines as displayed"),
+    ("#", "toggle line numbers"),
+    ("c", "cycle position: percent, line, row"),
+    ("v", "toggle showing control characters"),
+    ("q", "quit"),
+];
+
+/// Transcript-only bindings, listed after [`PAGER_KEY_HELP`].
+const TRANSCRIPT_KEY_HELP: &[(&str, &str)] = &[
+    ("g<number>⏎", "go to cell"),
+    ("gg/G", "go to the first/last cell"),
+    ("Esc", "edit the previous message"),
+    ("⏎", "edit the highlighted message"),
+    ("e", "expand or collapse the highlighted cell"),
+    ("o", "open the highlighted command's output"),
+    ("E", "edit the failed command"),
+    ("Y", "copy the command and its output"),
+    ("f", "open the command's working directory"),
+    ("Ctrl+E", "export the transcript as Markdown"),
+    ("]/[", "next/previous search hit"),
+    ("Ctrl+N/Ctrl+P", "next/previous diagnostic"),
+    ("A", "toggle showing only answers"),
+    ("C", "toggle grouping commands by directory"),
+    ("z", "toggle striped cells"),
+    ("M", "toggle the minimap"),
+    ("Alt+R", "start, pause or resume a replay"),
+    ("Alt+.", "reveal the next cell of a replay"),
 ];
 
 // Render a single line of key hints from (key, description) pairs.
@@ -85,6 +376,73 @@ struct PagerView {
     last_content_height: Option<usize>,
     /// If set, on next render ensure this chunk is visible.
     pending_scroll_chunk: Option<usize>,
+    /// Vim-style marks: letter -> wrapped-line scroll offset.
+    marks: HashMap<char, usize>,
+    /// Set after `m` or `'` while waiting for the mark letter.
+    pending_mark: Option<MarkAction>,
+    /// Set by Shift+End: sit at the bottom reached at that moment instead of
+    /// following content appended afterwards.
+    hold_position: bool,
+    /// Rows of the previous page kept visible when paging.
+    page_overlap: usize,
+    /// Widest width `ensure_wrapped` wraps at; wider viewports are clamped.
+    max_wrap_width: u16,
+    /// Key that writes a debug snapshot of the overlay to a file.
+    snapshot_key: (KeyCode, KeyModifiers),
+    /// Width the last `ensure_wrapped` wrapped at, after clamping.
+    last_wrap_width: Option<u16>,
+    /// Scroll offset drawn by the last `render`, used by `needs_redraw`.
+    rendered_scroll_offset: Option<usize>,
+    /// Show "line X of Y" in the bottom bar instead of a percentage.
+    show_line_position: bool,
+    /// Count wrapped rows ("row X of Y") rather than source lines in that
+    /// position.
+    count_wrapped_rows: bool,
+    /// Active search, scanned incrementally across renders.
+    search: Option<SearchState>,
+    wrap_mode: WrapMode,
+    /// Columns scrolled off the left edge while wrapping is off.
+    h_offset: usize,
+    /// Logical line drawn reversed, e.g. the diagnostic being visited.
+    focused_line: Option<usize>,
+    /// If set, on next render ensure this logical line is visible.
+    pending_scroll_line: Option<usize>,
+    /// Status shown at the left of the bottom bar when no search is active.
+    footer_note: Option<String>,
+    /// Copy wrapped lines as displayed (newline at each wrap point) instead of
+    /// as the original long lines.
+    copy_keeps_wraps: bool,
+    /// Freeze the view: no follow-bottom, and End/Shift+End do nothing, until
+    /// unlocked.
+    scroll_locked: bool,
+    /// Query being typed after `/`, until Enter or Esc.
+    search_prompt: Option<String>,
+    /// Scroll the current search match into view once it is known.
+    pending_search_jump: bool,
+    /// Draw control characters as their U+2400 control pictures.
+    show_control_pictures: bool,
+    /// Rows moved per mouse wheel notch.
+    wheel_lines: usize,
+    /// Message drawn in place of the overlay's key hints until the deadline.
+    transient_footer: Option<(String, Instant)>,
+    /// Show a gutter with logical (pre-wrap) line numbers.
+    show_line_numbers: bool,
+    /// Called with the new top row whenever the scroll position changes, so
+    /// an embedder can keep another pager in sync.
+    on_scroll: Option<Box<dyn FnMut(usize)>>,
+    /// Offset last reported to `on_scroll`.
+    notified_scroll_offset: Option<usize>,
+    /// Overlay-specific bindings shown on the help screen.
+    extra_help: &'static [(&'static str, &'static str)],
+    /// The `?` help screen, drawn and scrolled in place of the content while
+    /// open.
+    help: Option<Box<PagerView>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MarkAction {
+    Set,
+    Jump,
 }
 
 impl PagerView {
@@ -96,15 +454,54 @@ impl PagerView {
             wrap_cache: None,
             last_content_height: None,
             pending_scroll_chunk: None,
+            marks: HashMap::new(),
+            pending_mark: None,
+            hold_position: false,
+            page_overlap: 0,
+            max_wrap_width: DEFAULT_MAX_WRAP_WIDTH,
+            snapshot_key: (KeyCode::F(12), KeyModifiers::NONE),
+            last_wrap_width: None,
+            rendered_scroll_offset: None,
+            show_line_position: false,
+            count_wrapped_rows: false,
+            search: None,
+            wrap_mode: WrapMode::default(),
+            h_offset: 0,
+            focused_line: None,
+            pending_scroll_line: None,
+            footer_note: None,
+            copy_keeps_wraps: false,
+            scroll_locked: false,
+            search_prompt: None,
+            pending_search_jump: false,
+            show_control_pictures: false,
+            wheel_lines: DEFAULT_WHEEL_LINES,
+            transient_footer: None,
+            show_line_numbers: false,
+            on_scroll: None,
+            notified_scroll_offset: None,
+            extra_help: &[],
+            help: None,
         }
     }
 
     fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        if let Some(help) = self.help.as_mut() {
+            help.render(area, buf);
+            return;
+        }
         Clear.render(area, buf);
         self.render_header(area, buf);
         let content_area = self.scroll_area(area);
         self.update_last_content_height(content_area.height);
-        self.ensure_wrapped(content_area.width);
+        let gutter = self.gutter_width().min(content_area.width);
+        let text_area = Rect {
+            x: content_area.x + gutter,
+            width: content_area.width - gutter,
+            ..content_area
+        };
+        self.ensure_wrapped(text_area.width);
+        self.advance_search();
         // If there is a pending request to scroll a specific chunk into view,
         // satisfy it now that wrapping is up to date for this width.
         if let (Some(idx), Some(cache)) =
@@ -113,6 +510,13 @@ impl PagerView {
         {
             self.ensure_range_visible(range, content_area.height as usize, cache.wrapped.len());
         }
+        if let (Some(line), Some(cache)) =
+            (self.pending_scroll_line.take(), self.wrap_cache.as_ref())
+            && let Some(range) = cache.line_range(line)
+        {
+            self.ensure_range_visible(range, content_area.height as usize, cache.wrapped.len());
+        }
+        self.resolve_search_jump(content_area.height as usize);
         // Compute page bounds without holding an immutable borrow on cache while mutating self
         let wrapped_len = self
             .wrap_cache
@@ -124,26 +528,327 @@ impl PagerView {
             .min(wrapped_len.saturating_sub(content_area.height as usize));
         let start = self.scroll_offset;
         let end = (start + content_area.height as usize).min(wrapped_len);
+        let max_h_offset = self
+            .wrap_cache
+            .as_ref()
+            .map(|c| c.max_width.saturating_sub(text_area.width as usize))
+            .unwrap_or(0);
+        self.h_offset = if self.wrap_mode.clips_lines() {
+            self.h_offset.min(max_h_offset)
+        } else {
+            0
+        };
 
         let wrapped = self.cached();
-        let page = &wrapped[start..end];
-        self.render_content_page_prepared(content_area, buf, page);
+        let focused = self
+            .focused_line
+            .zip(self.wrap_cache.as_ref())
+            .and_then(|(line, cache)| cache.line_range(line))
+            .unwrap_or_default();
+        let query = self.search.as_ref().map(|search| search.query.as_str());
+        let page: Vec<Line<'static>> = (start..end)
+            .map(|idx| {
+                let line = match query {
+                    Some(query) => highlight_matches(wrapped[idx].clone(), query),
+                    None => wrapped[idx].clone(),
+                };
+                let line = if self.h_offset > 0 {
+                    skip_columns(line, self.h_offset)
+                } else {
+                    line
+                };
+                if focused.contains(&idx) {
+                    line.reversed()
+                } else {
+                    line
+                }
+            })
+            .collect();
+        if gutter > 0 {
+            self.render_line_numbers(
+                Rect {
+                    width: gutter,
+                    ..content_area
+                },
+                buf,
+                start..end,
+            );
+        }
+        self.render_content_page_prepared(text_area, buf, &page);
         self.render_bottom_bar(area, content_area, buf, wrapped);
+        self.rendered_scroll_offset = Some(self.scroll_offset);
+        // Clamping or a pending jump may have moved the view.
+        self.notify_scroll();
+    }
+
+    /// Content width the last render wrapped lines at, after the line number
+    /// gutter and the `max_wrap_width` clamp. `None` before the first render.
+    pub(crate) fn last_wrap_width(&self) -> Option<u16> {
+        self.last_wrap_width
+    }
+
+    /// Top row currently shown, with the follow-bottom sentinel resolved.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn scroll_offset(&self) -> usize {
+        self.effective_scroll_offset()
+    }
+
+    /// Scroll so `offset` is the top row; clamped to the content on the next
+    /// render.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn set_scroll_offset(&mut self, offset: usize) {
+        self.scroll_offset = offset;
+        self.notify_scroll();
+    }
+
+    /// Register `callback` to receive the new top row after every scroll,
+    /// whether from keys, the mouse or `set_scroll_offset`.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn set_on_scroll(&mut self, callback: impl FnMut(usize) + 'static) {
+        self.on_scroll = Some(Box::new(callback));
+        self.notified_scroll_offset = Some(self.effective_scroll_offset());
+    }
+
+    /// Report the offset to `on_scroll` if it changed since the last report.
+    /// Unchanged offsets are not reported, so two pagers mirroring each other
+    /// settle instead of ping-ponging.
+    fn notify_scroll(&mut self) {
+        let offset = self.effective_scroll_offset();
+        if self.notified_scroll_offset == Some(offset) {
+            return;
+        }
+        if let Some(callback) = self.on_scroll.as_mut() {
+            self.notified_scroll_offset = Some(offset);
+            callback(offset);
+        }
+    }
+
+    /// Whether the content or scroll position changed since the last `render`,
+    /// so embedders can skip drawing an unchanged pager. Resizes are not
+    /// tracked here; callers redraw on those anyway.
+    #[cfg_attr(not(test), allow(dead_code))]
+    fn needs_redraw(&self) -> bool {
+        let wrap_stale = self
+            .wrap_cache
+            .as_ref()
+            .is_none_or(|cache| cache.base_len != self.texts.len());
+        wrap_stale
+            || self.pending_scroll_chunk.is_some()
+            || self.rendered_scroll_offset != Some(self.scroll_offset)
+            || self.search_in_progress()
+    }
+
+    /// Begin searching for `query`. Matches are collected a bounded number of
+    /// lines per render; an empty query clears the search.
+    fn start_search(&mut self, query: &str) {
+        self.search = (!query.is_empty()).then(|| SearchState::new(query));
+    }
+
+    /// Rescan from the top, e.g. after the content was replaced.
+    fn restart_search(&mut self) {
+        if let Some(search) = self.search.as_mut() {
+            *search = SearchState::new(&search.query);
+        }
+    }
+
+    /// Scan the next batch of lines for the active search.
+    fn advance_search(&mut self) {
+        if let Some(search) = self.search.as_mut() {
+            search.advance(&self.texts, SEARCH_LINES_PER_FRAME);
+        }
+    }
+
+    /// Whether keys should bypass overlay shortcuts: a mark letter or the
+    /// search prompt is pending.
+    fn captures_keys(&self) -> bool {
+        self.help.is_some() || self.pending_mark.is_some() || self.search_prompt.is_some()
+    }
+
+    /// Pager listing every key binding, with the key column aligned.
+    fn help_view(&self) -> PagerView {
+        let snapshot_key = describe_key(self.snapshot_key);
+        let pairs: Vec<(&str, &str)> = PAGER_KEY_HELP
+            .iter()
+            .chain(self.extra_help)
+            .copied()
+            .chain([
+                (snapshot_key.as_str(), "save a debug snapshot"),
+                ("?/Esc", "close this help"),
+            ])
+            .collect();
+        let key_width = pairs.iter().map(|(key, _)| key.width()).max().unwrap_or(0);
+        let lines: Vec<Line<'static>> = pairs
+            .into_iter()
+            .map(|(key, desc)| {
+                let pad = key_width - key.width();
+                Line::from(vec![
+                    format!(" {key}{} ", " ".repeat(pad)).cyan(),
+                    format!(" {desc}").into(),
+                ])
+            })
+            .collect();
+        let mut view = PagerView::new(vec![Text::from(lines)], "K E Y S".to_string(), 0);
+        view.wheel_lines = self.wheel_lines;
+        view
+    }
+
+    /// Route a key to the open help screen: `?`, Esc or `q` closes it, and
+    /// anything else scrolls it. Returns false when no help is open.
+    fn handle_help_key(&mut self, key_event: KeyEvent, viewport_area: Rect) -> bool {
+        let Some(help) = self.help.as_mut() else {
+            return false;
+        };
+        match key_event.code {
+            KeyCode::Char('?') | KeyCode::Esc | KeyCode::Char('q')
+                if key_event.kind == KeyEventKind::Press =>
+            {
+                self.help = None;
+            }
+            _ => {
+                help.apply_key_event(key_event, viewport_area);
+            }
+        }
+        true
+    }
+
+    /// Edit the `/` search prompt: Enter runs the query and jumps to the first
+    /// match at or below the top of the view, Esc cancels. Returns true when
+    /// the key was consumed.
+    fn handle_search_prompt_key(&mut self, key_event: KeyEvent) -> bool {
+        if key_event.kind == KeyEventKind::Release {
+            return false;
+        }
+        let Some(prompt) = self.search_prompt.as_mut() else {
+            return false;
+        };
+        match key_event.code {
+            KeyCode::Enter => {
+                let query = std::mem::take(prompt);
+                self.search_prompt = None;
+                self.start_search(&query);
+                self.pending_search_jump = self.search.is_some();
+            }
+            KeyCode::Esc => self.search_prompt = None,
+            KeyCode::Backspace => {
+                prompt.pop();
+            }
+            KeyCode::Char(c) if !key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                prompt.push(c);
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Move to the next (or previous) search match, wrapping around.
+    fn step_search(&mut self, forward: bool) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        let count = search.matches.len();
+        search.current = match (search.current, forward) {
+            _ if count == 0 => None,
+            // Nothing visited yet: the jump picks the first match in view.
+            (None, true) => None,
+            (None, false) => Some(count - 1),
+            (Some(i), true) => Some((i + 1) % count),
+            (Some(i), false) => Some((i + count - 1) % count),
+        };
+        self.pending_search_jump = true;
+    }
+
+    /// Scroll the current match into view, first choosing the earliest match
+    /// at or below the top line when none is selected. Waits while matches
+    /// are still being scanned.
+    fn resolve_search_jump(&mut self, viewport_height: usize) {
+        if !self.pending_search_jump {
+            return;
+        }
+        let top = self.effective_scroll_offset();
+        let (Some(search), Some(cache)) = (self.search.as_mut(), self.wrap_cache.as_ref()) else {
+            self.pending_search_jump = false;
+            return;
+        };
+        if search.current.is_none() {
+            let top_line = cache
+                .line_starts
+                .partition_point(|&start| start <= top)
+                .saturating_sub(1);
+            match search.matches.iter().position(|m| m.line >= top_line) {
+                Some(idx) => search.current = Some(idx),
+                None if !search.is_complete(&self.texts) => return,
+                None if search.matches.is_empty() => {
+                    self.pending_search_jump = false;
+                    return;
+                }
+                None => search.current = Some(0),
+            }
+        }
+        let row = search
+            .current
+            .and_then(|idx| search.matches.get(idx))
+            .and_then(|m| cache.match_row(*m));
+        let total = cache.wrapped.len();
+        self.pending_search_jump = false;
+        if let Some(row) = row {
+            self.ensure_range_visible(row..row + 1, viewport_height, total);
+        }
+    }
+
+    /// Whether a search still has lines left to scan, so callers keep
+    /// scheduling frames until it completes.
+    fn search_in_progress(&self) -> bool {
+        self.search
+            .as_ref()
+            .is_some_and(|search| !search.is_complete(&self.texts))
     }
 
     fn render_header(&self, area: Rect, buf: &mut Buffer) {
         Span::from("/ ".repeat(area.width as usize / 2))
             .dim()
             .render_ref(area, buf);
-        let header = format!("/ {}", self.title);
+        let lock = if self.scroll_locked { " 🔒" } else { "" };
+        let header = format!("/ {}{lock}", self.title);
         header.dim().render_ref(area, buf);
     }
 
     // Removed unused render_content_page (replaced by render_content_page_prepared)
 
+    /// Columns taken by the line-number gutter: enough digits for the last
+    /// logical line plus a separating space, or 0 when hidden.
+    fn gutter_width(&self) -> u16 {
+        if !self.show_line_numbers {
+            return 0;
+        }
+        let total: usize = self.texts.iter().map(|text| text.lines.len()).sum();
+        let digits = total.max(1).to_string().len();
+        (digits + 1).min(u16::MAX as usize) as u16
+    }
+
+    /// Right-aligned logical line numbers for the wrapped `rows`; rows that
+    /// continue a wrapped line get a blank gutter.
+    fn render_line_numbers(&self, area: Rect, buf: &mut Buffer, rows: std::ops::Range<usize>) {
+        let Some(cache) = self.wrap_cache.as_ref() else {
+            return;
+        };
+        let digits = area.width.saturating_sub(1) as usize;
+        for (y, row) in (area.y..area.bottom()).zip(rows) {
+            if let Ok(line) = cache.line_starts.binary_search(&row) {
+                Span::from(format!("{:>digits$}", line + 1))
+                    .dim()
+                    .render_ref(Rect::new(area.x, y, area.width, 1), buf);
+            }
+        }
+    }
+
     fn render_content_page_prepared(&self, area: Rect, buf: &mut Buffer, page: &[Line<'static>]) {
         Clear.render(area, buf);
-        Paragraph::new(page.to_vec()).render_ref(area, buf);
+        let lines: Vec<Line<'static>> = if self.show_control_pictures {
+            page.iter().cloned().map(with_control_pictures).collect()
+        } else {
+            page.to_vec()
+        };
+        Paragraph::new(lines).render_ref(area, buf);
 
         let visible = page.len();
         if visible < area.height as usize {
@@ -170,32 +875,94 @@ impl PagerView {
         Span::from("─".repeat(sep_rect.width as usize))
             .dim()
             .render_ref(sep_rect, buf);
-        let percent = if wrapped.is_empty() {
-            100
+        let max_scroll = wrapped.len().saturating_sub(content_area.height as usize);
+        let pct_text = if self.show_line_position && self.count_wrapped_rows {
+            let row = (self.scroll_offset.min(max_scroll) + 1).min(wrapped.len());
+            format!(" row {row} of {} ", wrapped.len())
+        } else if self.show_line_position {
+            let (line, total) = self.logical_line_position();
+            format!(" line {line} of {total} ")
+        } else if max_scroll == 0 {
+            // Everything fits, so a percentage would be ambiguous with "at end".
+            " All ".to_string()
         } else {
-            let max_scroll = wrapped.len().saturating_sub(content_area.height as usize);
-            if max_scroll == 0 {
+            let offset = self.scroll_offset.min(max_scroll);
+            // Only report 100% when the last line is actually in view.
+            let percent = if offset == max_scroll {
                 100
             } else {
-                (((self.scroll_offset.min(max_scroll)) as f32 / max_scroll as f32) * 100.0).round()
-                    as u8
+                ((offset as f32 / max_scroll as f32) * 100.0)
+                    .round()
+                    .min(99.0) as u8
+            };
+            format!(" {percent}% ")
+        };
+        let pct_text = if self.wrap_mode.clips_lines() {
+            format!(" col {}{pct_text}", self.h_offset + 1)
+        } else {
+            pct_text
+        };
+        let note_text = if let Some(prompt) = self.search_prompt.as_ref() {
+            Some(format!(" /{prompt} "))
+        } else if let Some(search) = self.search.as_ref() {
+            let count = search.matches.len();
+            let more = if search.is_complete(&self.texts) {
+                ""
+            } else {
+                "+"
+            };
+            match search.current {
+                Some(current) => Some(format!(" {}/{count}{more} ", current + 1)),
+                None => {
+                    let noun = if count == 1 { "match" } else { "matches" };
+                    Some(format!(" {count}{more} {noun} "))
+                }
             }
+        } else {
+            self.footer_note.as_ref().map(|note| format!(" {note} "))
         };
-        let pct_text = format!(" {percent}% ");
+        if let Some(note_text) = note_text {
+            let note_w = note_text.chars().count() as u16;
+            Span::from(note_text).render_ref(
+                Rect::new(sep_rect.x + 1, sep_rect.y, note_w.min(sep_rect.width), 1),
+                buf,
+            );
+        }
         let pct_w = pct_text.chars().count() as u16;
-        let pct_x = sep_rect.x + sep_rect.width - pct_w - 1;
+        let pct_x = sep_rect.x + sep_rect.width.saturating_sub(pct_w + 1);
         Span::from(pct_text)
             .dim()
             .render_ref(Rect::new(pct_x, sep_rect.y, pct_w, 1), buf);
     }
 
     fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) -> Result<()> {
+        if self.apply_key_event(key_event, tui.terminal.viewport_area) {
+            tui.frame_requester()
+                .schedule_frame_in(Duration::from_millis(16));
+            self.schedule_transient_footer_frames(&tui.frame_requester());
+        }
+        Ok(())
+    }
+
+    /// Apply a key to the view; `viewport_area` sizes page steps. Returns
+    /// whether the key was handled.
+    fn apply_key_event(&mut self, key_event: KeyEvent, viewport_area: Rect) -> bool {
+        if self.handle_help_key(key_event, viewport_area) {
+            return true;
+        }
+        if self.handle_search_prompt_key(key_event) || self.handle_mark_key(key_event) {
+            self.notify_scroll();
+            return true;
+        }
+        // Scrolling and navigation arms release a Shift+End pause; other keys
+        // (copy, toggles) leave it in place.
         match key_event {
             KeyEvent {
                 code: KeyCode::Up,
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
             } => {
+                self.hold_position = false;
                 self.scroll_offset = self.scroll_offset.saturating_sub(1);
             }
             KeyEvent {
@@ -203,6 +970,7 @@ impl PagerView {
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
             } => {
+                self.hold_position = false;
                 self.scroll_offset = self.scroll_offset.saturating_add(1);
             }
             KeyEvent {
@@ -210,38 +978,467 @@ impl PagerView {
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
             } => {
-                let area = self.scroll_area(tui.terminal.viewport_area);
-                self.scroll_offset = self.scroll_offset.saturating_sub(area.height as usize);
+                self.hold_position = false;
+                let area = self.scroll_area(viewport_area);
+                self.scroll_offset = self.scroll_offset.saturating_sub(self.page_step(area));
             }
             KeyEvent {
                 code: KeyCode::PageDown | KeyCode::Char(' '),
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
             } => {
-                let area = self.scroll_area(tui.terminal.viewport_area);
-                self.scroll_offset = self.scroll_offset.saturating_add(area.height as usize);
+                self.hold_position = false;
+                let area = self.scroll_area(viewport_area);
+                self.scroll_offset = self.scroll_offset.saturating_add(self.page_step(area));
+            }
+            KeyEvent {
+                code: code @ (KeyCode::Char('d') | KeyCode::Char('u')),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } => {
+                self.hold_position = false;
+                let step = self.half_page_step(self.scroll_area(viewport_area));
+                self.scroll_offset = if code == KeyCode::Char('d') {
+                    self.scroll_offset.saturating_add(step)
+                } else {
+                    self.scroll_offset.saturating_sub(step)
+                };
+            }
+            KeyEvent {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.force_rewrap();
+            }
+            KeyEvent {
+                code: KeyCode::Char('w'),
+                modifiers: KeyModifiers::NONE,
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.wrap_mode = match self.wrap_mode {
+                    WrapMode::All => WrapMode::LongLinesOnly,
+                    WrapMode::LongLinesOnly => WrapMode::None,
+                    WrapMode::None => WrapMode::All,
+                };
+            }
+            KeyEvent {
+                code: KeyCode::Left,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } if self.wrap_mode.clips_lines() => {
+                self.h_offset = self.h_offset.saturating_sub(HORIZONTAL_SCROLL_STEP);
+            }
+            KeyEvent {
+                code: KeyCode::Right,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } if self.wrap_mode.clips_lines() => {
+                // Clamped to the widest line on the next render.
+                self.h_offset = self.h_offset.saturating_add(HORIZONTAL_SCROLL_STEP);
+            }
+            KeyEvent {
+                code: KeyCode::Char('/'),
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.search_prompt = Some(String::new());
+            }
+            KeyEvent {
+                code: KeyCode::Char('n'),
+                modifiers: KeyModifiers::NONE,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } if self.search.is_some() => {
+                self.hold_position = false;
+                self.step_search(true);
+            }
+            KeyEvent {
+                code: KeyCode::Char('N'),
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } if self.search.is_some() => {
+                self.hold_position = false;
+                self.step_search(false);
+            }
+            KeyEvent {
+                code: KeyCode::Char('y'),
+                modifiers: KeyModifiers::NONE,
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.copy_visible_page();
+            }
+            KeyEvent {
+                code: KeyCode::Char('#'),
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.show_line_numbers = !self.show_line_numbers;
+            }
+            KeyEvent {
+                code: KeyCode::Char('c'),
+                modifiers: KeyModifiers::NONE,
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                // Cycle percentage -> source line -> wrapped row.
+                if !self.show_line_position {
+                    self.show_line_position = true;
+                } else if !self.count_wrapped_rows {
+                    self.count_wrapped_rows = true;
+                } else {
+                    self.show_line_position = false;
+                    self.count_wrapped_rows = false;
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Char('v'),
+                modifiers: KeyModifiers::NONE,
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.show_control_pictures = !self.show_control_pictures;
+            }
+            KeyEvent {
+                code: KeyCode::Char('W'),
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.toggle_copy_keeps_wraps();
+            }
+            KeyEvent {
+                code: KeyCode::Char('?'),
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.help = Some(Box::new(self.help_view()));
             }
             KeyEvent {
                 code: KeyCode::Home,
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
             } => {
+                self.hold_position = false;
                 self.scroll_offset = 0;
             }
+            KeyEvent {
+                code: KeyCode::ScrollLock | KeyCode::Char('L'),
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.toggle_scroll_lock();
+            }
+            KeyEvent {
+                code: KeyCode::End,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } if self.scroll_locked => {}
             KeyEvent {
                 code: KeyCode::End,
+                modifiers: KeyModifiers::SHIFT,
                 kind: KeyEventKind::Press | KeyEventKind::Repeat,
                 ..
             } => {
-                self.scroll_offset = usize::MAX;
+                self.jump_to_current_end();
             }
-            _ => {
-                return Ok(());
+            KeyEvent {
+                code: KeyCode::End,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } => {
+                self.hold_position = false;
+                self.scroll_offset = usize::MAX;
             }
+            _ => return false,
+        }
+        self.notify_scroll();
+        true
+    }
+
+    /// Scroll by `wheel_lines` per wheel notch. The offset is clamped to the
+    /// content on the next render, like keyboard scrolling.
+    fn handle_mouse_event(&mut self, tui: &mut tui::Tui, mouse_event: MouseEvent) {
+        if let Some(help) = self.help.as_mut() {
+            help.handle_mouse_event(tui, mouse_event);
+            return;
+        }
+        if !self.scroll_by_wheel(mouse_event.kind) {
+            return;
         }
+        self.notify_scroll();
         tui.frame_requester()
             .schedule_frame_in(Duration::from_millis(16));
-        Ok(())
+    }
+
+    fn scroll_by_wheel(&mut self, kind: MouseEventKind) -> bool {
+        match kind {
+            MouseEventKind::ScrollUp => {
+                // Resolve the follow-bottom sentinel before stepping back.
+                self.scroll_offset = self
+                    .effective_scroll_offset()
+                    .saturating_sub(self.wheel_lines);
+            }
+            MouseEventKind::ScrollDown => {
+                self.scroll_offset = self.scroll_offset.saturating_add(self.wheel_lines);
+            }
+            _ => return false,
+        }
+        self.hold_position = false;
+        true
+    }
+
+    /// Handle vim-style marks: `m<letter>` records the current position and
+    /// `'<letter>` jumps back to it. Returns true when the key was consumed.
+    fn handle_mark_key(&mut self, key_event: KeyEvent) -> bool {
+        if key_event.kind != KeyEventKind::Press {
+            return false;
+        }
+        if let Some(action) = self.pending_mark.take() {
+            // Any key completes the command; only letters name a mark.
+            if let KeyCode::Char(letter) = key_event.code
+                && letter.is_ascii_alphabetic()
+            {
+                match action {
+                    MarkAction::Set => {
+                        let offset = self.effective_scroll_offset();
+                        self.marks.insert(letter, offset);
+                    }
+                    MarkAction::Jump => {
+                        if let Some(&offset) = self.marks.get(&letter) {
+                            self.scroll_offset = offset;
+                        }
+                    }
+                }
+            }
+            return true;
+        }
+        match key_event.code {
+            KeyCode::Char('m') if key_event.modifiers == KeyModifiers::NONE => {
+                self.pending_mark = Some(MarkAction::Set);
+                true
+            }
+            KeyCode::Char('\'') => {
+                self.pending_mark = Some(MarkAction::Jump);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Concrete scroll offset, resolving the `usize::MAX` follow-bottom
+    /// sentinel against the last rendered layout.
+    fn effective_scroll_offset(&self) -> usize {
+        self.scroll_offset.min(self.max_scroll_offset())
+    }
+
+    fn max_scroll_offset(&self) -> usize {
+        let wrapped_len = self
+            .wrap_cache
+            .as_ref()
+            .map(|c| c.wrapped.len())
+            .unwrap_or(0);
+        let height = self.last_content_height.unwrap_or(0);
+        wrapped_len.saturating_sub(height)
+    }
+
+    /// Jump to the current bottom as a fixed offset so later content does not
+    /// scroll the view (unlike End, which re-engages follow).
+    fn jump_to_current_end(&mut self) {
+        self.scroll_offset = self.max_scroll_offset();
+        self.hold_position = true;
+    }
+
+    /// Whether appended content should keep the view pinned to the bottom.
+    fn should_follow_bottom(&self) -> bool {
+        !self.scroll_locked && !self.hold_position && self.is_scrolled_to_bottom()
+    }
+
+    /// Engage or release the scroll lock. Engaging resolves the follow-bottom
+    /// sentinel so the current rows stay put as content is appended.
+    fn toggle_scroll_lock(&mut self) {
+        self.scroll_locked = !self.scroll_locked;
+        if self.scroll_locked {
+            self.scroll_offset = self.effective_scroll_offset();
+        }
+    }
+
+    /// Show `message` in place of the key hints for `duration`.
+    fn set_transient_footer(&mut self, message: String, duration: Duration) {
+        self.transient_footer = Some((message, Instant::now() + duration));
+    }
+
+    /// Briefly confirm an action, e.g. "copied 12 lines", in place of the key
+    /// hints.
+    fn confirm(&mut self, message: String) {
+        self.set_transient_footer(message, CONFIRMATION_DURATION);
+    }
+
+    /// Draw the live transient footer now and again once it expires, so the
+    /// key hints come back.
+    fn schedule_transient_footer_frames(&self, frame_requester: &FrameRequester) {
+        let Some((_, until)) = self.transient_footer.as_ref() else {
+            return;
+        };
+        let Some(remaining) = until.checked_duration_since(Instant::now()) else {
+            return;
+        };
+        frame_requester.schedule_frame();
+        frame_requester.schedule_frame_in(remaining);
+    }
+
+    /// Scroll and layout metadata followed by the plain text of every line.
+    fn debug_snapshot(&self) -> String {
+        let mut out = format!(
+            "title: {}\nscroll_offset: {}\ncontent_height: {}\n",
+            self.title,
+            self.effective_scroll_offset(),
+            self.last_content_height.unwrap_or(0),
+        );
+        if let Some(width) = self.last_wrap_width() {
+            out.push_str(&format!("wrap_width: {width}\n"));
+        }
+        if let Some(cache) = self.wrap_cache.as_ref() {
+            out.push_str(&format!("wrapped_lines: {}\n", cache.wrapped.len()));
+        }
+        out.push('\n');
+        for text in &self.texts {
+            out.push_str(&lines_to_plain_text(&text.lines));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Draw the transient footer, if one is still live, over the hint rows.
+    /// Returns false once it has expired so the caller draws its hints.
+    fn render_transient_footer(&self, area: Rect, buf: &mut Buffer) -> bool {
+        let Some((message, _)) = self
+            .transient_footer
+            .as_ref()
+            .filter(|(_, until)| Instant::now() < *until)
+        else {
+            return false;
+        };
+        Span::from(format!(" {message}")).render_ref(Rect::new(area.x, area.y, area.width, 1), buf);
+        true
+    }
+
+    /// Text of the logical lines drawn by the last render, as it should land
+    /// on the clipboard. A line cut off at the top or bottom edge is copied
+    /// whole.
+    fn visible_page_text(&self) -> String {
+        let Some(cache) = self.wrap_cache.as_ref() else {
+            return String::new();
+        };
+        let start = self.effective_scroll_offset().min(cache.wrapped.len());
+        let end = (start + self.last_content_height.unwrap_or(0)).min(cache.wrapped.len());
+        let first = cache
+            .line_starts
+            .partition_point(|&row| row <= start)
+            .saturating_sub(1);
+        let last = cache.line_starts.partition_point(|&row| row < end);
+        let lines: Vec<String> = self
+            .texts
+            .iter()
+            .flat_map(|text| text.lines.iter())
+            .skip(first)
+            .take(last.saturating_sub(first))
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+        self.text_for_copy(&lines.join("\n"))
+    }
+
+    /// Copy the visible page to the clipboard, confirming (or reporting the
+    /// failure, e.g. no clipboard over SSH) in the bottom bar.
+    fn copy_visible_page(&mut self) {
+        let text = self.visible_page_text();
+        let message = match copy_text_to_clipboard(&text) {
+            Ok(()) => {
+                let count = text.lines().count();
+                let noun = if count == 1 { "line" } else { "lines" };
+                format!("copied {count} {noun}")
+            }
+            Err(err) => {
+                tracing::warn!("failed to copy pager page: {err}");
+                "copy failed: clipboard unavailable".to_string()
+            }
+        };
+        self.confirm(message);
+    }
+
+    fn toggle_copy_keeps_wraps(&mut self) {
+        self.copy_keeps_wraps = !self.copy_keeps_wraps;
+        self.confirm(
+            if self.copy_keeps_wraps {
+                "copy: keep wraps"
+            } else {
+                "copy: unwrap"
+            }
+            .to_string(),
+        );
+    }
+
+    /// `text` as it should land on the clipboard: unchanged, or with a newline
+    /// at every point where the pager soft-wrapped it.
+    fn text_for_copy(&self, text: &str) -> String {
+        let Some(cache) = self.wrap_cache.as_ref().filter(|_| self.copy_keeps_wraps) else {
+            return text.to_string();
+        };
+        let width = cache.width as usize;
+        let mut out = String::with_capacity(text.len());
+        for line in text.split_inclusive('\n') {
+            let (body, newline) = match line.strip_suffix('\n') {
+                Some(body) => (body, "\n"),
+                None => (line, ""),
+            };
+            let body = Line::from(body);
+            let rows: Vec<String> = crate::wrapping::word_wrap_line(&body, width)
+                .iter()
+                .map(|row| row.spans.iter().map(|span| span.content.as_ref()).collect())
+                .collect();
+            out.push_str(&rows.join("\n"));
+            out.push_str(newline);
+        }
+        out
+    }
+
+    /// Drop the wrap cache so the next render re-wraps at the current width,
+    /// for terminals that report font/zoom size changes late.
+    fn force_rewrap(&mut self) {
+        self.wrap_cache = None;
+    }
+
+    /// Rows moved by PageUp/PageDown, keeping `page_overlap` rows of context.
+    fn page_step(&self, content_area: Rect) -> usize {
+        (content_area.height as usize)
+            .saturating_sub(self.page_overlap)
+            .max(1)
+    }
+
+    /// Rows moved by Ctrl+D/Ctrl+U: half the content height.
+    fn half_page_step(&self, content_area: Rect) -> usize {
+        (content_area.height as usize / 2).max(1)
+    }
+
+    /// 1-based logical line at the top of the viewport and the total number of
+    /// logical (unwrapped) lines.
+    fn logical_line_position(&self) -> (usize, usize) {
+        let Some(cache) = self.wrap_cache.as_ref() else {
+            return (0, 0);
+        };
+        let total = cache.line_starts.len();
+        let line = cache
+            .line_starts
+            .partition_point(|&start| start <= self.scroll_offset);
+        (line.max(1).min(total), total)
     }
 
     fn update_last_content_height(&mut self, height: u16) {
@@ -256,20 +1453,356 @@ impl PagerView {
     }
 }
 
+/// How `ensure_wrapped` breaks lines wider than the viewport.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum WrapMode {
+    /// Wrap every line to the viewport width.
+    #[default]
+    All,
+    /// Wrap only lines longer than `LONG_LINE_WRAP_FACTOR` viewports; shorter
+    /// overflowing lines are clipped at the right edge and scrolled
+    /// horizontally with Left/Right.
+    LongLinesOnly,
+    /// Never wrap; lines are clipped at the viewport and scrolled
+    /// horizontally with Left/Right.
+    None,
+}
+
+impl WrapMode {
+    /// Whether some lines can extend past the viewport, so Left/Right scroll.
+    fn clips_lines(self) -> bool {
+        self != WrapMode::All
+    }
+}
+
+/// Columns moved per Left/Right press while lines are clipped.
+const HORIZONTAL_SCROLL_STEP: usize = 8;
+
+const LONG_LINE_WRAP_FACTOR: usize = 2;
+
+/// Default cap on the wrap width. Far wider than any real terminal, but
+/// bounds the cache if a resize race reports an absurd width.
+const DEFAULT_MAX_WRAP_WIDTH: u16 = 1000;
+
+const DEFAULT_WHEEL_LINES: usize = 3;
+
+/// How long a confirmation such as "copied 12 lines" replaces the key hints.
+const CONFIRMATION_DURATION: Duration = Duration::from_secs(1);
+
+/// Lines scanned per render while a search is in progress, so huge content
+/// does not stall the UI.
+const SEARCH_LINES_PER_FRAME: usize = 2_000;
+
+/// A search hit in logical-line coordinates, which survive re-wrapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SearchMatch {
+    /// Logical line index across all text chunks.
+    line: usize,
+    /// Byte offset of the match within the line's plain text.
+    start: usize,
+}
+
+/// Incremental, ASCII case-insensitive search over the pager's logical lines.
+struct SearchState {
+    query: String,
+    matches: Vec<SearchMatch>,
+    /// Index into `matches` last jumped to with Enter or n/N.
+    current: Option<usize>,
+    /// Next (chunk, line within chunk) to scan.
+    cursor: (usize, usize),
+    /// Logical line index of `cursor`.
+    scanned: usize,
+}
+
+impl SearchState {
+    fn new(query: &str) -> Self {
+        Self {
+            query: query.to_ascii_lowercase(),
+            matches: Vec::new(),
+            current: None,
+            cursor: (0, 0),
+            scanned: 0,
+        }
+    }
+
+    /// Scan up to `budget` more lines, appending any matches found.
+    fn advance(&mut self, texts: &[Text<'static>], budget: usize) {
+        let mut remaining = budget;
+        while remaining > 0 {
+            let (chunk, line_idx) = self.cursor;
+            let Some(text) = texts.get(chunk) else {
+                return;
+            };
+            let Some(line) = text.lines.get(line_idx) else {
+                self.cursor = (chunk + 1, 0);
+                continue;
+            };
+            let plain: String = line
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+                .to_ascii_lowercase();
+            self.matches.extend(
+                plain
+                    .match_indices(&self.query)
+                    .map(|(start, _)| SearchMatch {
+                        line: self.scanned,
+                        start,
+                    }),
+            );
+            self.cursor = (chunk, line_idx + 1);
+            self.scanned += 1;
+            remaining -= 1;
+        }
+    }
+
+    fn is_complete(&self, texts: &[Text<'static>]) -> bool {
+        let (chunk, line_idx) = self.cursor;
+        texts
+            .get(chunk)
+            .is_none_or(|text| line_idx >= text.lines.len())
+            && texts
+                .iter()
+                .skip(chunk + 1)
+                .all(|text| text.lines.is_empty())
+    }
+}
+
+/// Replace C0 control characters and DEL with their dim U+2400 control
+/// pictures, so otherwise invisible bytes show up.
+fn with_control_pictures(line: Line<'static>) -> Line<'static> {
+    let picture = |c: char| match c {
+        '\u{0}'..='\u{1f}' => char::from_u32(0x2400 + c as u32),
+        '\u{7f}' => Some('\u{2421}'),
+        _ => None,
+    };
+    if !line
+        .spans
+        .iter()
+        .any(|span| span.content.chars().any(|c| picture(c).is_some()))
+    {
+        return line;
+    }
+    let mut spans: Vec<Span<'static>> = Vec::with_capacity(line.spans.len());
+    for span in &line.spans {
+        let mut plain = String::new();
+        for c in span.content.chars() {
+            match picture(c) {
+                Some(glyph) => {
+                    if !plain.is_empty() {
+                        spans.push(Span::styled(std::mem::take(&mut plain), span.style));
+                    }
+                    spans.push(Span::styled(glyph.to_string(), span.style).dim());
+                }
+                None => plain.push(c),
+            }
+        }
+        if !plain.is_empty() {
+            spans.push(Span::styled(plain, span.style));
+        }
+    }
+    Line { spans, ..line }
+}
+
+/// Keep the first `max_chars` characters of `line`, replacing the rest with a
+/// dim `…(+N chars)` marker.
+fn truncate_line_chars(line: Line<'static>, max_chars: usize) -> Line<'static> {
+    let total: usize = line.spans.iter().map(|s| s.content.chars().count()).sum();
+    if total <= max_chars {
+        return line;
+    }
+    let mut remaining = max_chars;
+    let mut spans = Vec::new();
+    for span in line.spans {
+        if remaining == 0 {
+            break;
+        }
+        let len = span.content.chars().count();
+        if len <= remaining {
+            remaining -= len;
+            spans.push(span);
+        } else {
+            let kept: String = span.content.chars().take(remaining).collect();
+            spans.push(Span::styled(kept, span.style));
+            remaining = 0;
+        }
+    }
+    spans.push(format!("…(+{} chars)", total - max_chars).dim());
+    Line { spans, ..line }
+}
+
+/// Drop the first `cols` display columns of `line`, keeping span styles. A
+/// wide character straddling the cut is dropped whole.
+fn skip_columns(line: Line<'static>, cols: usize) -> Line<'static> {
+    let mut remaining = cols;
+    let mut spans = Vec::with_capacity(line.spans.len());
+    for span in line.spans {
+        if remaining == 0 {
+            spans.push(span);
+            continue;
+        }
+        let (_, rest, skipped) = take_prefix_by_width(&span.content, remaining);
+        remaining -= skipped;
+        let mut rest = rest.chars();
+        if remaining > 0 && skipped < span.width() {
+            // The next character is wider than what is left to skip.
+            rest.next();
+            remaining = 0;
+        }
+        let rest = rest.as_str();
+        if !rest.is_empty() {
+            spans.push(Span::styled(rest.to_string(), span.style));
+        }
+    }
+    Line { spans, ..line }
+}
+
+/// Reverse every ASCII case-insensitive occurrence of `query` in `line`,
+/// splitting spans at match boundaries.
+fn highlight_matches(line: Line<'static>, query: &str) -> Line<'static> {
+    if query.is_empty() {
+        return line;
+    }
+    let plain: String = line
+        .spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let reversed = Style::new().add_modifier(Modifier::REVERSED);
+    let ranges: Vec<(std::ops::Range<usize>, Style)> = plain
+        .match_indices(query)
+        .map(|(start, found)| (start..start + found.len(), reversed))
+        .collect();
+    style_ranges(line, &ranges)
+}
+
+/// Compile user-supplied `(pattern, style)` pairs into transcript highlight
+/// rules. The first invalid pattern is reported instead of panicking.
+pub(crate) fn compile_highlight_rules<'a>(
+    patterns: impl IntoIterator<Item = (&'a str, Style)>,
+) -> std::result::Result<Vec<(Regex, Style)>, String> {
+    patterns
+        .into_iter()
+        .map(|(pattern, style)| {
+            Regex::new(pattern)
+                .map(|re| (re, style))
+                .map_err(|err| format!("invalid highlight pattern `{pattern}`: {err}"))
+        })
+        .collect()
+}
+
+/// Color every match of each highlight rule, with later rules patched over
+/// earlier ones where matches overlap.
+fn apply_highlight_rules(line: Line<'static>, rules: &[(Regex, Style)]) -> Line<'static> {
+    let plain: String = line
+        .spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect();
+    let ranges: Vec<(std::ops::Range<usize>, Style)> = rules
+        .iter()
+        .flat_map(|(re, style)| {
+            re.find_iter(&plain)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.range(), *style))
+        })
+        .collect();
+    style_ranges(line, &ranges)
+}
+
+/// Patch `style` onto the bytes of each `(range, style)` pair, where ranges
+/// index the concatenated span contents. Spans are split at range edges.
+fn style_ranges(line: Line<'static>, ranges: &[(std::ops::Range<usize>, Style)]) -> Line<'static> {
+    if ranges.is_empty() {
+        return line;
+    }
+    let mut spans: Vec<Span<'static>> = Vec::with_capacity(line.spans.len() + 2 * ranges.len());
+    let mut offset = 0;
+    for span in &line.spans {
+        let content = span.content.as_ref();
+        let end = offset + content.len();
+        // Cut points inside this span, in span-local byte offsets.
+        let mut cuts: Vec<usize> = ranges
+            .iter()
+            .flat_map(|(r, _)| [r.start, r.end])
+            .filter(|&cut| cut > offset && cut < end)
+            .map(|cut| cut - offset)
+            .collect();
+        cuts.sort_unstable();
+        cuts.dedup();
+        let mut piece_start = 0;
+        for piece_end in cuts.into_iter().chain(std::iter::once(content.len())) {
+            let piece = &content[piece_start..piece_end];
+            if !piece.is_empty() {
+                let absolute = offset + piece_start;
+                let style = ranges
+                    .iter()
+                    .filter(|(r, _)| r.contains(&absolute))
+                    .fold(span.style, |acc, (_, style)| acc.patch(*style));
+                spans.push(Span::styled(piece.to_string(), style));
+            }
+            piece_start = piece_end;
+        }
+        offset = end;
+    }
+    Line { spans, ..line }
+}
+
 #[derive(Debug, Clone)]
 struct WrapCache {
     width: u16,
     wrapped: Vec<Line<'static>>,
     /// For each input Text chunk, the inclusive-excluded range of wrapped lines produced.
     chunk_ranges: Vec<std::ops::Range<usize>>,
+    /// Wrapped index at which each logical line begins.
+    line_starts: Vec<usize>,
     base_len: usize,
+    wrap_mode: WrapMode,
+    /// Width of the widest wrapped line, bounding horizontal scrolling.
+    max_width: usize,
+}
+
+impl WrapCache {
+    /// Wrapped row holding the start of `m`. A match that spans a wrap
+    /// boundary maps to its first row.
+    fn match_row(&self, m: SearchMatch) -> Option<usize> {
+        let range = self.line_range(m.line)?;
+        let mut consumed = 0;
+        for row in range.clone() {
+            consumed += self.wrapped[row]
+                .spans
+                .iter()
+                .map(|span| span.content.len())
+                .sum::<usize>();
+            if m.start < consumed {
+                return Some(row);
+            }
+        }
+        range.last()
+    }
+
+    /// Wrapped rows produced by logical line `line`.
+    fn line_range(&self, line: usize) -> Option<std::ops::Range<usize>> {
+        let start = *self.line_starts.get(line)?;
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .copied()
+            .unwrap_or(self.wrapped.len());
+        Some(start..end)
+    }
 }
 
 impl PagerView {
     fn ensure_wrapped(&mut self, width: u16) {
-        let width = width.max(1);
+        let width = width.clamp(1, self.max_wrap_width.max(1));
+        self.last_wrap_width = Some(width);
         let needs = match self.wrap_cache {
-            Some(ref c) => c.width != width || c.base_len != self.texts.len(),
+            Some(ref c) => {
+                c.width != width || c.base_len != self.texts.len() || c.wrap_mode != self.wrap_mode
+            }
             None => true,
         };
         if !needs {
@@ -277,20 +1810,38 @@ impl PagerView {
         }
         let mut wrapped: Vec<Line<'static>> = Vec::new();
         let mut chunk_ranges: Vec<std::ops::Range<usize>> = Vec::with_capacity(self.texts.len());
+        let mut line_starts: Vec<usize> = Vec::new();
         for text in &self.texts {
             let start = wrapped.len();
             for line in &text.lines {
-                let ws = crate::wrapping::word_wrap_line(line, width as usize);
-                push_owned_lines(&ws, &mut wrapped);
+                line_starts.push(wrapped.len());
+                let keep_unwrapped = match self.wrap_mode {
+                    WrapMode::All => false,
+                    WrapMode::LongLinesOnly => {
+                        line.width() <= LONG_LINE_WRAP_FACTOR * width as usize
+                    }
+                    WrapMode::None => true,
+                };
+                if keep_unwrapped {
+                    // Rendered without wrapping, so anything past the edge is clipped.
+                    wrapped.push(line.clone());
+                } else {
+                    let ws = crate::wrapping::word_wrap_line(line, width as usize);
+                    push_owned_lines(&ws, &mut wrapped);
+                }
             }
             let end = wrapped.len();
             chunk_ranges.push(start..end);
         }
+        let max_width = wrapped.iter().map(Line::width).max().unwrap_or(0);
         self.wrap_cache = Some(WrapCache {
             width,
             wrapped,
             chunk_ranges,
+            line_starts,
+            max_width,
             base_len: self.texts.len(),
+            wrap_mode: self.wrap_mode,
         });
     }
 
@@ -320,6 +1871,12 @@ impl PagerView {
         self.scroll_offset >= max_scroll
     }
 
+    /// Highlight a logical line and scroll it into view on next render.
+    fn focus_line(&mut self, line: usize) {
+        self.focused_line = Some(line);
+        self.pending_scroll_line = Some(line);
+    }
+
     /// Request that the given text chunk index be scrolled into view on next render.
     fn scroll_chunk_into_view(&mut self, chunk_index: usize) {
         self.pending_scroll_chunk = Some(chunk_index);
@@ -351,156 +1908,1044 @@ impl PagerView {
     }
 }
 
+/// Width budget for the cwd in group-by-cwd headings; deeper paths are
+/// middle-elided.
+const CWD_HEADING_MAX_WIDTH: usize = 48;
+
+/// Parse a key spec such as `f12`, `ctrl+s` or `alt+d`: `f1`-`f12` or a
+/// single character, optionally prefixed with `ctrl+` and/or `alt+`.
+fn parse_key_spec(spec: &str) -> Option<(KeyCode, KeyModifiers)> {
+    let mut parts: Vec<&str> = spec.trim().split('+').collect();
+    let key = parts.pop()?;
+    let mut modifiers = KeyModifiers::NONE;
+    for part in parts {
+        modifiers |= match part.to_ascii_lowercase().as_str() {
+            "ctrl" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            _ => return None,
+        };
+    }
+    let function_key = key
+        .to_ascii_lowercase()
+        .strip_prefix('f')
+        .and_then(|n| n.parse::<u8>().ok())
+        .filter(|n| (1..=12).contains(n));
+    let code = match function_key {
+        Some(n) => KeyCode::F(n),
+        None => {
+            let mut chars = key.chars();
+            let c = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            KeyCode::Char(c)
+        }
+    };
+    Some((code, modifiers))
+}
+
+/// Label for a key as written in the help screen, e.g. `F12` or `Ctrl+S`.
+fn describe_key((code, modifiers): (KeyCode, KeyModifiers)) -> String {
+    let mut label = String::new();
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        label.push_str("Ctrl+");
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        label.push_str("Alt+");
+    }
+    match code {
+        KeyCode::F(n) => label.push_str(&format!("F{n}")),
+        KeyCode::Char(c) => label.extend(c.to_uppercase()),
+        other => label.push_str(&format!("{other:?}")),
+    }
+    label
+}
+
+/// How long the "snapshot saved to X" footer stays up.
+const SNAPSHOT_FOOTER_DURATION: Duration = Duration::from_secs(4);
+
+/// Delay between revealed cells while a transcript replay is playing.
+const REPLAY_STEP_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Progressive reveal of transcript cells, used for demos.
+struct ReplayState {
+    /// Number of leading cells currently revealed.
+    revealed: usize,
+    playing: bool,
+    last_step: Instant,
+}
+
 pub(crate) struct TranscriptOverlay {
     view: PagerView,
     cells: Vec<Arc<dyn HistoryCell>>,
     highlight_cell: Option<usize>,
+    /// Cells shown with their full transcript lines instead of the abbreviated ones.
+    expanded: HashSet<usize>,
+    replay: Option<ReplayState>,
+    show_minimap: bool,
+    group_by_cwd: bool,
+    /// Render only assistant answers; other cells produce empty chunks so
+    /// cell indices still line up with `view.texts`.
+    answers_only: bool,
+    /// Background applied to every other cell, when zebra striping is on.
+    stripe_style: Option<Style>,
+    /// Patterns colored wherever they appear in cell lines, applied in order.
+    highlight_rules: Vec<(Regex, Style)>,
+    /// Context the last chunk in `view.texts` was rendered with, so appended
+    /// cells continue from it without rescanning the transcript.
+    last_cell_context: Option<CellContext>,
+    follow: TranscriptFollow,
+    /// Retention cap on real cells; older cells collapse into a marker.
+    max_cells: Option<usize>,
+    /// Characters kept from a single line; longer lines are cut so one huge
+    /// line cannot blow up the wrap cache.
+    max_line_chars: Option<usize>,
+    /// Number of cells dropped by the cap. While non-zero, `cells[0]` is the
+    /// marker standing in for them.
+    hidden_cells: usize,
+    /// Index into `diagnostic_locations()` last visited with Ctrl+N/Ctrl+P.
+    diagnostic_index: Option<usize>,
+    /// Index into `search_hit_locations()` last visited with `]`/`[`.
+    search_hit_index: Option<usize>,
+    /// Full output of one exec cell, opened with `o` on top of the
+    /// transcript so huge output stays out of the transcript's wrap cache.
+    output_view: Option<Box<StaticOverlay>>,
+    /// The most recent notice, so an identical repeat updates its counter
+    /// instead of appending another cell.
+    last_notice: Option<Box<RepeatedNotice>>,
+    /// Cell number typed after `g`, until Enter jumps to it.
+    pending_goto: Option<String>,
+    /// Where to send a failed command picked with `E` for editing in the
+    /// composer. Unset in tests and standalone transcripts.
+    app_event_tx: Option<AppEventSender>,
     is_done: bool,
 }
 
+#[derive(Debug)]
+struct RepeatedNotice {
+    text: String,
+    style: NoticeStyle,
+    count: usize,
+    cell: Arc<dyn HistoryCell>,
+}
+
+/// Stands in for the oldest transcript cells dropped by the retention cap.
+#[derive(Debug)]
+struct HiddenCellsMarker {
+    count: usize,
+}
+
+impl HistoryCell for HiddenCellsMarker {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        let noun = if self.count == 1 { "cell" } else { "cells" };
+        vec![
+            format!("… {} earlier {noun} hidden", self.count)
+                .dim()
+                .into(),
+        ]
+    }
+}
+
+/// 64-bit FNV-1a. Unlike the std hashers it is specified to give the same
+/// result on every run and platform.
+struct Fnv1aHasher(u64);
+
+impl Default for Fnv1aHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1aHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// What rendering a cell needs to know about the cells before it, carried
+/// from one cell to the next so rendering the transcript stays linear.
+#[derive(Clone, Debug, Default)]
+struct CellContext {
+    /// Some cell comes before this one.
+    after_any: bool,
+    /// Some assistant answer comes before this one.
+    after_answer: bool,
+    /// Cells before this one that are not stream continuations.
+    starts_before: usize,
+    /// Working directory of the closest earlier exec cell.
+    previous_cwd: Option<PathBuf>,
+}
+
+impl CellContext {
+    /// Context of the cell following `cell`, which was rendered with `self`.
+    fn advance(&self, cell: &Arc<dyn HistoryCell>) -> CellContext {
+        CellContext {
+            after_any: true,
+            after_answer: self.after_answer || cell.is_final_answer(),
+            starts_before: self.starts_before + usize::from(!cell.is_stream_continuation()),
+            previous_cwd: exec_cell_cwd(cell)
+                .map(Path::to_path_buf)
+                .or_else(|| self.previous_cwd.clone()),
+        }
+    }
+}
+
+fn exec_cell_cwd(cell: &Arc<dyn HistoryCell>) -> Option<&Path> {
+    cell.as_any()
+        .downcast_ref::<ExecCell>()
+        .and_then(|exec| exec.cwd.as_deref())
+}
+
 impl TranscriptOverlay {
     pub(crate) fn new(transcript_cells: Vec<Arc<dyn HistoryCell>>) -> Self {
-        Self {
-            view: PagerView::new(
-                Self::render_cells_to_texts(&transcript_cells, None),
-                "T R A N S C R I P T".to_string(),
-                usize::MAX,
-            ),
+        let mut overlay = Self {
+            view: PagerView::new(Vec::new(), "T R A N S C R I P T".to_string(), usize::MAX),
             cells: transcript_cells,
             highlight_cell: None,
+            expanded: HashSet::new(),
+            replay: None,
+            show_minimap: false,
+            group_by_cwd: false,
+            answers_only: false,
+            stripe_style: None,
+            highlight_rules: Vec::new(),
+            last_cell_context: None,
+            follow: TranscriptFollow::default(),
+            max_cells: None,
+            max_line_chars: None,
+            hidden_cells: 0,
+            diagnostic_index: None,
+            search_hit_index: None,
+            output_view: None,
+            last_notice: None,
+            pending_goto: None,
+            app_event_tx: None,
             is_done: false,
-        }
+        };
+        overlay.view.extra_help = TRANSCRIPT_KEY_HELP;
+        overlay.rebuild_texts();
+        overlay
     }
 
-    fn render_cells_to_texts(
-        cells: &[Arc<dyn HistoryCell>],
-        highlight_cell: Option<usize>,
-    ) -> Vec<Text<'static>> {
-        let mut texts: Vec<Text<'static>> = Vec::new();
-        let mut first = true;
-        for (idx, cell) in cells.iter().enumerate() {
-            let mut lines: Vec<Line<'static>> = Vec::new();
-            if !cell.is_stream_continuation() && !first {
-                lines.push(Line::from(""));
-            }
-            let cell_lines = if Some(idx) == highlight_cell {
-                cell.transcript_lines()
-                    .into_iter()
-                    .map(Stylize::reversed)
-                    .collect()
-            } else {
-                cell.transcript_lines()
-            };
-            lines.extend(cell_lines);
-            texts.push(Text::from(lines));
-            first = false;
-        }
-        texts
+    /// Build a transcript from a saved `ResponseItem` history, e.g. a rollout
+    /// file. See [`crate::history_replay`] for which items are rendered and
+    /// how unsupported ones appear.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn from_response_items(items: Vec<ResponseItem>) -> Self {
+        Self::new(cells_from_response_items(items))
     }
 
-    pub(crate) fn insert_cell(&mut self, cell: Arc<dyn HistoryCell>) {
-        let follow_bottom = self.view.is_scrolled_to_bottom();
-        // Append as a new Text chunk (with a separating blank if needed)
+    fn render_cells_to_texts(&mut self) -> Vec<Text<'static>> {
+        self.last_cell_context = None;
+        (0..self.visible_cells().len())
+            .map(|idx| self.render_next_cell(idx))
+            .collect()
+    }
+
+    /// Render `cells[idx]`, which follows the last rendered chunk, and
+    /// remember its context for the cell after it.
+    fn render_next_cell(&mut self, idx: usize) -> Text<'static> {
+        let context = match (&self.last_cell_context, idx.checked_sub(1)) {
+            (Some(previous), Some(prev_idx)) => previous.advance(&self.cells[prev_idx]),
+            _ => CellContext::default(),
+        };
+        let text = self.render_cell_text(idx, &context);
+        self.last_cell_context = Some(context);
+        text
+    }
+
+    /// Render one cell as a text chunk, including the blank separator and, when
+    /// grouping by directory, a header for a changed working directory.
+    fn render_cell_text(&self, idx: usize, context: &CellContext) -> Text<'static> {
+        let cell = &self.cells[idx];
         let mut lines: Vec<Line<'static>> = Vec::new();
-        if !cell.is_stream_continuation() && !self.cells.is_empty() {
+        if self.answers_only && !cell.is_final_answer() {
+            return Text::from(lines);
+        }
+        let has_previous = if self.answers_only {
+            context.after_answer
+        } else {
+            context.after_any
+        };
+        if !cell.is_stream_continuation() && has_previous {
             lines.push(Line::from(""));
         }
-        lines.extend(cell.transcript_lines());
-        self.view.texts.push(Text::from(lines));
-        self.cells.push(cell);
-        self.view.wrap_cache = None;
-        if follow_bottom {
-            self.view.scroll_offset = usize::MAX;
+        if self.group_by_cwd
+            && let Some(cwd) = exec_cell_cwd(cell)
+            && context.previous_cwd.as_deref() != Some(cwd)
+        {
+            let cwd = format_directory_display(cwd, Some(CWD_HEADING_MAX_WIDTH));
+            lines.push(format!("— {cwd} —").dim().into());
         }
+        let expanded = self.expanded.contains(&idx);
+        let cell_lines = if expanded {
+            cell.full_transcript_lines()
+                .unwrap_or_else(|| cell.transcript_lines())
+        } else {
+            cell.transcript_lines()
+        };
+        // An expanded cell shows everything, including over-long lines.
+        let cell_lines = match self.max_line_chars {
+            Some(max_chars) if !expanded => cell_lines
+                .into_iter()
+                .map(|line| truncate_line_chars(line, max_chars))
+                .collect(),
+            _ => cell_lines,
+        };
+        let cell_lines = match self.stripe_style {
+            // Continuation cells keep the stripe of the cell they continue.
+            Some(stripe)
+                if (context.starts_before + usize::from(!cell.is_stream_continuation())) % 2
+                    == 0 =>
+            {
+                cell_lines
+                    .into_iter()
+                    .map(|line| {
+                        let style = line.style.patch(stripe);
+                        line.style(style)
+                    })
+                    .collect()
+            }
+            _ => cell_lines,
+        };
+        let cell_lines = if self.highlight_rules.is_empty() {
+            cell_lines
+        } else {
+            cell_lines
+                .into_iter()
+                .map(|line| apply_highlight_rules(line, &self.highlight_rules))
+                .collect()
+        };
+        let cell_lines = if Some(idx) == self.highlight_cell {
+            cell_lines.into_iter().map(Stylize::reversed).collect()
+        } else {
+            cell_lines
+        };
+        lines.extend(cell_lines);
+        Text::from(lines)
     }
 
-    pub(crate) fn set_highlight_cell(&mut self, cell: Option<usize>) {
-        self.highlight_cell = cell;
-        self.view.wrap_cache = None;
-        self.view.texts = Self::render_cells_to_texts(&self.cells, self.highlight_cell);
-        if let Some(idx) = self.highlight_cell {
-            self.view.scroll_chunk_into_view(idx);
+    /// Append a styled UI notice (e.g. "model switched") as a new cell,
+    /// following the same scroll policy as any other inserted cell. Repeating
+    /// the notice that is still the last cell updates it in place with a
+    /// counter, e.g. `rate limited, retrying (×3)`.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn push_notice(&mut self, text: impl Into<String>, style: NoticeStyle) {
+        let text = text.into();
+        if let Some(notice) = self.last_notice.as_mut()
+            && notice.text == text
+            && notice.style == style
+            && self
+                .cells
+                .last()
+                .is_some_and(|last| Arc::ptr_eq(last, &notice.cell))
+        {
+            notice.count += 1;
+            notice.cell = Arc::new(new_notice(format!("{text} (×{})", notice.count), style));
+            let idx = self.cells.len() - 1;
+            self.cells[idx] = notice.cell.clone();
+            // While replaying, the cell may not be revealed yet.
+            if idx < self.view.texts.len()
+                && let Some(context) = self.last_cell_context.clone()
+            {
+                self.view.texts[idx] = self.render_cell_text(idx, &context);
+                self.view.wrap_cache = None;
+            }
+            return;
         }
+        let cell: Arc<dyn HistoryCell> = Arc::new(new_notice(text.clone(), style));
+        self.insert_cells(vec![cell.clone()]);
+        self.last_notice = Some(Box::new(RepeatedNotice {
+            text,
+            style,
+            count: 1,
+            cell,
+        }));
     }
 
-    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
-        let line1 = Rect::new(area.x, area.y, area.width, 1);
-        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
-        render_key_hints(line1, buf, PAGER_KEY_HINTS);
-        let mut pairs: Vec<(&str, &str)> = vec![("q", "quit"), ("Esc", "edit prev")];
-        if self.highlight_cell.is_some() {
-            pairs.push(("⏎", "edit message"));
+    /// Append several cells at once, invalidating the wrap cache a single time.
+    /// Used to flush cells that streamed in between frames.
+    pub(crate) fn insert_cells(&mut self, cells: Vec<Arc<dyn HistoryCell>>) {
+        if cells.is_empty() {
+            return;
         }
-        render_key_hints(line2, buf, &pairs);
+        if self.replay.is_some() {
+            // The replay reveals new cells as it reaches them.
+            self.cells.extend(cells);
+            return;
+        }
+        let follow_bottom = match self.follow {
+            _ if self.view.scroll_locked => false,
+            TranscriptFollow::FollowWhenAtBottom => self.view.should_follow_bottom(),
+            TranscriptFollow::AlwaysJump => true,
+            TranscriptFollow::NeverJump => {
+                // Pin the follow-bottom sentinel so new content does not pull
+                // the view along.
+                self.view.scroll_offset = self.view.effective_scroll_offset();
+                false
+            }
+        };
+        // Append each as a new Text chunk (with a separating blank if needed)
+        let first_new = self.cells.len();
+        self.cells.extend(cells);
+        for idx in first_new..self.cells.len() {
+            let text = self.render_next_cell(idx);
+            self.view.texts.push(text);
+        }
+        if follow_bottom {
+            self.view.scroll_offset = usize::MAX;
+        }
+        // Trim while the previous wrap cache can still anchor the scroll.
+        self.enforce_cell_limit();
+        self.view.wrap_cache = None;
     }
 
-    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
-        let top_h = area.height.saturating_sub(3);
-        let top = Rect::new(area.x, area.y, area.width, top_h);
-        let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
-        self.view.render(top, buf);
-        self.render_hints(bottom, buf);
+    /// Map an index into the full transcript to an index into `cells`, or
+    /// `None` when that cell was dropped by the retention cap.
+    fn local_cell_index(&self, idx: usize) -> Option<usize> {
+        if self.hidden_cells == 0 {
+            Some(idx)
+        } else {
+            idx.checked_sub(self.hidden_cells).map(|i| i + 1)
+        }
     }
-}
 
-impl TranscriptOverlay {
-    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
-        match event {
-            TuiEvent::Key(key_event) => match key_event {
-                KeyEvent {
-                    code: KeyCode::Char('q'),
-                    kind: KeyEventKind::Press,
-                    ..
+    /// Drop the oldest cells beyond `max_cells`, replacing them with a marker
+    /// and keeping highlight, expansion and scroll position on the same cells.
+    fn enforce_cell_limit(&mut self) {
+        let Some(max_cells) = self.max_cells else {
+            return;
+        };
+        if self.replay.is_some() {
+            return;
+        }
+        let marker = usize::from(self.hidden_cells > 0);
+        let excess = (self.cells.len() - marker).saturating_sub(max_cells);
+        if excess == 0 {
+            return;
+        }
+        let first_kept = marker + excess;
+        // Wrapped rows that disappear above the first kept cell, less the
+        // marker's own row.
+        let removed_rows = self
+            .view
+            .wrap_cache
+            .as_ref()
+            .and_then(|cache| cache.chunk_ranges.get(first_kept))
+            .map(|range| range.start.saturating_sub(1));
+
+        self.cells.drain(marker..first_kept);
+        self.hidden_cells += excess;
+        let marker_cell: Arc<dyn HistoryCell> = Arc::new(HiddenCellsMarker {
+            count: self.hidden_cells,
+        });
+        if marker == 1 {
+            self.cells[0] = marker_cell;
+        } else {
+            self.cells.insert(0, marker_cell);
+        }
+
+        // Kept cells move from `first_kept + i` to `1 + i`.
+        let shift = |idx: usize| idx.checked_sub(first_kept).map(|i| i + 1);
+        self.highlight_cell = self.highlight_cell.and_then(shift);
+        self.expanded = self.expanded.iter().copied().filter_map(shift).collect();
+        if self.view.scroll_offset != usize::MAX
+            && let Some(rows) = removed_rows
+        {
+            self.view.scroll_offset = self.view.scroll_offset.saturating_sub(rows);
+        }
+        self.rebuild_texts();
+    }
+
+    pub(crate) fn set_highlight_cell(&mut self, cell: Option<usize>) {
+        self.highlight_cell = cell.and_then(|idx| self.local_cell_index(idx));
+        self.rebuild_texts();
+        if let Some(idx) = self.highlight_cell {
+            self.view.scroll_chunk_into_view(idx);
+        }
+    }
+
+    fn debug_snapshot(&self) -> String {
+        // The nested output view is what the user is looking at.
+        if let Some(output_view) = self.output_view.as_ref() {
+            return format!(
+                "overlay: transcript output\n{}",
+                output_view.view.debug_snapshot()
+            );
+        }
+        format!(
+            "overlay: transcript\ncells: {}\nhidden_cells: {}\n{}",
+            self.cells.len(),
+            self.hidden_cells,
+            self.view.debug_snapshot()
+        )
+    }
+
+    /// Scroll to the cell with stable `id`, wherever earlier drops or
+    /// filtering have moved it. Returns whether the cell is shown.
+    pub(crate) fn reveal_cell_by_id(&mut self, id: &CellId) -> bool {
+        let Some(idx) = self
+            .visible_cells()
+            .iter()
+            .position(|cell| cell.id().as_ref() == Some(id))
+        else {
+            return false;
+        };
+        self.view.scroll_chunk_into_view(idx);
+        true
+    }
+
+    /// Color every match of each rule in the transcript. Rules are applied in
+    /// order beneath the selected-cell highlight; see
+    /// [`compile_highlight_rules`] for building them from user patterns.
+    pub(crate) fn set_highlight_rules(&mut self, rules: Vec<(Regex, Style)>) {
+        self.highlight_rules = rules;
+        self.rebuild_texts();
+    }
+
+    /// The whole transcript as Markdown: commands as fenced blocks with their
+    /// output, patches as diffs and answers as prose. Streamed continuations
+    /// join their message without a blank line.
+    pub(crate) fn export_markdown(&self) -> String {
+        let mut out = String::new();
+        for cell in &self.cells {
+            let markdown = cell.transcript_markdown();
+            let markdown = markdown.trim_end();
+            if markdown.is_empty() {
+                continue;
+            }
+            if !out.is_empty() {
+                out.push_str(if cell.is_stream_continuation() {
+                    "\n"
+                } else {
+                    "\n\n"
+                });
+            }
+            out.push_str(markdown);
+        }
+        out.push('\n');
+        out
+    }
+
+    /// Deterministic hash of every cell's plain transcript text, in order, so
+    /// callers can cheaply tell whether the transcript changed.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn content_hash(&self) -> u64 {
+        let mut hasher = Fnv1aHasher::default();
+        for cell in &self.cells {
+            for line in cell.transcript_lines() {
+                for span in &line.spans {
+                    hasher.write(span.content.as_bytes());
                 }
-                | KeyEvent {
-                    code: KeyCode::Char('t'),
-                    modifiers: crossterm::event::KeyModifiers::CONTROL,
-                    kind: KeyEventKind::Press,
-                    ..
+                hasher.write_u8(b'\n');
+            }
+            // Separate cells so moving a line across a cell boundary changes
+            // the hash.
+            hasher.write_u8(0);
+        }
+        hasher.finish()
+    }
+
+    /// Cells currently shown, which is a prefix of `cells` while replaying.
+    fn visible_cells(&self) -> &[Arc<dyn HistoryCell>] {
+        match &self.replay {
+            Some(replay) => &self.cells[..replay.revealed.min(self.cells.len())],
+            None => &self.cells,
+        }
+    }
+
+    fn rebuild_texts(&mut self) {
+        self.view.texts = self.render_cells_to_texts();
+        self.view.wrap_cache = None;
+        self.view.restart_search();
+        // Line numbers may have shifted, so start diagnostic and search hit
+        // navigation over.
+        self.diagnostic_index = None;
+        self.search_hit_index = None;
+        self.view.focused_line = None;
+        self.view.footer_note = None;
+    }
+
+    /// Plain text of every exec cell with the logical line it starts on, in
+    /// transcript order.
+    fn exec_cell_texts(&self) -> Vec<(usize, String)> {
+        let mut texts = Vec::new();
+        let mut first_line = 0;
+        for (cell, text) in self.visible_cells().iter().zip(&self.view.texts) {
+            if cell.as_any().is::<ExecCell>() {
+                let plain = text
+                    .lines
+                    .iter()
+                    .map(|line| {
+                        line.spans
+                            .iter()
+                            .map(|span| span.content.as_ref())
+                            .collect::<String>()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                texts.push((first_line, plain));
+            }
+            first_line += text.lines.len();
+        }
+        texts
+    }
+
+    /// Logical line of every compiler diagnostic printed by an exec cell,
+    /// with the diagnostic itself, in transcript order.
+    fn diagnostic_locations(&self) -> Vec<(usize, Diagnostic)> {
+        self.exec_cell_texts()
+            .into_iter()
+            .flat_map(|(first_line, plain)| {
+                parse_diagnostics(&plain)
+                    .into_iter()
+                    .map(move |d| (first_line + d.output_line, d))
+            })
+            .collect()
+    }
+
+    /// Logical line of every `path:line:col:match` hit printed by an exec
+    /// cell (e.g. `rg --vimgrep`), in transcript order.
+    fn search_hit_locations(&self) -> Vec<(usize, SearchHit)> {
+        self.exec_cell_texts()
+            .into_iter()
+            .flat_map(|(first_line, plain)| {
+                parse_search_hits(&plain)
+                    .into_iter()
+                    .map(move |hit| (first_line + hit.output_line, hit))
+            })
+            .collect()
+    }
+
+    /// Move to the next (or previous) search hit, wrapping around, and show
+    /// its exact location in the bottom bar.
+    fn step_search_hit(&mut self, forward: bool) {
+        let mut locations = self.search_hit_locations();
+        if locations.is_empty() {
+            self.view.footer_note = Some("no search hits".to_string());
+            return;
+        }
+        let count = locations.len();
+        let idx = match (self.search_hit_index, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+        };
+        let (line, hit) = locations.swap_remove(idx);
+        self.search_hit_index = Some(idx);
+        self.view.focus_line(line);
+        self.view.footer_note = Some(format!("hit {} of {count}: {}", idx + 1, hit.location()));
+    }
+
+    /// Move to the next (or previous) diagnostic, wrapping around, and report
+    /// the position in the bottom bar.
+    fn step_diagnostic(&mut self, forward: bool) {
+        let mut locations = self.diagnostic_locations();
+        if locations.is_empty() {
+            self.view.footer_note = Some("no diagnostics".to_string());
+            return;
+        }
+        let count = locations.len();
+        let idx = match (self.diagnostic_index, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+        };
+        let (line, diagnostic) = locations.swap_remove(idx);
+        self.diagnostic_index = Some(idx);
+        self.view.focus_line(line);
+        self.view.footer_note = Some(format!(
+            "diagnostic {} of {count}: {} at {}: {}",
+            idx + 1,
+            diagnostic.severity.label(),
+            diagnostic.location(),
+            diagnostic.message
+        ));
+    }
+
+    /// Number of cells in the full transcript, counting those hidden by the
+    /// retention cap but not the marker standing in for them.
+    fn total_cells(&self) -> usize {
+        self.cells.len() - usize::from(self.hidden_cells > 0) + self.hidden_cells
+    }
+
+    /// Scroll the cell at `idx` (0-based, full transcript) into view. Cells
+    /// dropped by the retention cap land on the marker.
+    fn jump_to_cell(&mut self, idx: usize) {
+        let last = self.visible_cells().len().saturating_sub(1);
+        let local = self.local_cell_index(idx).unwrap_or(0).min(last);
+        self.view.scroll_chunk_into_view(local);
+    }
+
+    /// Cell jumps: `g` followed by a number and Enter goes to that cell, `gg`
+    /// to the first and `G` to the last. Returns true when the key was
+    /// consumed.
+    fn handle_goto_key(&mut self, key_event: KeyEvent) -> bool {
+        if key_event.kind != KeyEventKind::Press {
+            return false;
+        }
+        if let Some(digits) = self.pending_goto.as_mut() {
+            match key_event.code {
+                KeyCode::Char(c) if c.is_ascii_digit() => {
+                    digits.push(c);
+                    self.view.footer_note = Some(format!("go to cell {digits}"));
+                    return true;
                 }
-                | KeyEvent {
-                    code: KeyCode::Char('c'),
-                    modifiers: crossterm::event::KeyModifiers::CONTROL,
-                    kind: KeyEventKind::Press,
-                    ..
-                } => {
-                    self.is_done = true;
-                    Ok(())
+                KeyCode::Backspace => {
+                    digits.pop();
+                    self.view.footer_note = Some(format!("go to cell {digits}"));
+                    return true;
                 }
-                other => self.view.handle_key_event(tui, other),
-            },
+                KeyCode::Char('g') if digits.is_empty() => self.jump_to_cell(0),
+                KeyCode::Enter => {
+                    if let Ok(n) = digits.parse::<usize>()
+                        && n > 0
+                    {
+                        self.jump_to_cell(n - 1);
+                    }
+                }
+                // Esc or any other key cancels.
+                _ => {}
+            }
+            self.pending_goto = None;
+            self.view.footer_note = None;
+            return true;
+        }
+        match key_event.code {
+            KeyCode::Char('g') if key_event.modifiers == KeyModifiers::NONE => {
+                self.pending_goto = Some(String::new());
+                self.view.footer_note = Some("go to cell ".to_string());
+                true
+            }
+            KeyCode::Char('G') => {
+                self.jump_to_cell(self.total_cells().saturating_sub(1));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Toggle the highlighted cell between its abbreviated and full transcript
+    /// lines.
+    fn toggle_expand_highlighted(&mut self) {
+        let Some(idx) = self.highlight_cell else {
+            return;
+        };
+        if !self.expanded.remove(&idx) {
+            self.expanded.insert(idx);
+        }
+        self.rebuild_texts();
+        self.view.scroll_chunk_into_view(idx);
+    }
+
+    /// The exec cell an action applies to: the highlighted cell if it is one,
+    /// otherwise the last exec cell overlapping the viewport.
+    fn target_exec_cell(&self) -> Option<&ExecCell> {
+        if let Some(idx) = self.highlight_cell {
+            return self
+                .cells
+                .get(idx)
+                .and_then(|cell| cell.as_any().downcast_ref::<ExecCell>());
+        }
+        self.viewport_cells()
+            .into_iter()
+            .rev()
+            .find_map(|idx| self.cells[idx].as_any().downcast_ref::<ExecCell>())
+    }
+
+    /// Indices into `cells` of the cells overlapping the viewport as of the
+    /// last render.
+    fn viewport_cells(&self) -> Vec<usize> {
+        let Some(cache) = self.view.wrap_cache.as_ref() else {
+            return Vec::new();
+        };
+        let top = self.view.effective_scroll_offset();
+        let bottom = top + self.view.last_content_height.unwrap_or(0);
+        cache
+            .chunk_ranges
+            .iter()
+            .take(self.visible_cells().len())
+            .enumerate()
+            .filter(|(_, range)| range.start < bottom && range.end > top)
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Full-transcript indices of the cells overlapping the viewport as of the
+    /// last render, e.g. to tell which cells the user actually looked at. The
+    /// marker for cells dropped by the retention cap is not included.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn visible_cell_indices(&self) -> Vec<usize> {
+        self.viewport_cells()
+            .into_iter()
+            .filter_map(|idx| match self.hidden_cells {
+                0 => Some(idx),
+                hidden => idx.checked_sub(1).map(|i| i + hidden),
+            })
+            .collect()
+    }
+
+    /// Copy the target exec cell's command and output for a bug report.
+    fn copy_command_with_output(&mut self) {
+        let Some(cell) = self.target_exec_cell() else {
+            self.view.confirm("no command in view".to_string());
+            return;
+        };
+        let text = self.view.text_for_copy(&cell.command_with_output_text());
+        let message = match copy_text_to_clipboard(&text) {
+            Ok(()) => "copied command output".to_string(),
+            Err(err) => {
+                tracing::warn!("failed to copy command output: {err}");
+                "copy failed: clipboard unavailable".to_string()
+            }
+        };
+        self.view.confirm(message);
+    }
+
+    /// The last failed command of the target exec cell, quoted so it can be
+    /// pasted back into a shell.
+    fn failed_command(&self) -> Option<String> {
+        self.target_exec_cell()?
+            .iter_calls()
+            .filter(|call| call.output.as_ref().is_some_and(|o| o.exit_code != 0))
+            .last()
+            .map(|call| strip_bash_lc_and_escape(&call.command))
+    }
+
+    /// Send the target exec cell's failed command to the composer so it can be
+    /// tweaked and resubmitted.
+    fn edit_failed_command(&mut self) {
+        let Some(command) = self.failed_command() else {
+            self.view.confirm("no failed command here".to_string());
+            return;
+        };
+        let Some(tx) = self.app_event_tx.as_ref() else {
+            return;
+        };
+        tx.send(AppEvent::PrefillComposer(command));
+    }
+
+    /// Write the transcript as Markdown to a temp file and add its path to
+    /// the history.
+    fn export_markdown_file(&mut self) {
+        self.export_markdown_with(|markdown| {
+            let path = std::env::temp_dir().join(format!(
+                "codex-transcript-{}.md",
+                chrono::Utc::now().format("%Y%m%dT%H%M%SZ")
+            ));
+            std::fs::write(&path, markdown)?;
+            Ok(path)
+        });
+    }
+
+    fn export_markdown_with(&mut self, write: impl FnOnce(&str) -> std::io::Result<PathBuf>) {
+        let path = match write(&self.export_markdown()) {
+            Ok(path) => path,
+            Err(err) => {
+                tracing::warn!("failed to export transcript: {err}");
+                self.view.confirm("export failed".to_string());
+                return;
+            }
+        };
+        self.view.confirm(format!("exported to {}", path.display()));
+        if let Some(tx) = self.app_event_tx.as_ref() {
+            tx.send(AppEvent::InsertHistoryCell(Box::new(new_info_event(
+                format!("Transcript exported to {}", path.display()),
+                None,
+            ))));
+        }
+    }
+
+    /// Open the target exec cell's working directory in the system file
+    /// manager, reporting the outcome in the bottom bar.
+    fn open_cwd(&mut self) {
+        self.open_cwd_with(open_in_file_manager);
+    }
+
+    fn open_cwd_with(&mut self, opener: impl FnOnce(&Path) -> std::io::Result<()>) {
+        let Some(cwd) = self.target_exec_cell().and_then(|cell| cell.cwd.clone()) else {
+            self.view
+                .confirm("no directory for this command".to_string());
+            return;
+        };
+        let message = match opener(&cwd) {
+            Ok(()) => format!("opened {}", cwd.display()),
+            Err(err) => {
+                tracing::warn!("failed to open {}: {err}", cwd.display());
+                "could not open file manager".to_string()
+            }
+        };
+        self.view.confirm(message);
+    }
+
+    /// Open the target exec cell's full output in its own pager.
+    fn open_output_view(&mut self) {
+        let Some(cell) = self.target_exec_cell() else {
+            return;
+        };
+        let mut overlay =
+            StaticOverlay::with_title(cell.full_output_lines(), "O U T P U T".to_string());
+        overlay.view.page_overlap = self.view.page_overlap;
+        overlay.view.max_wrap_width = self.view.max_wrap_width;
+        overlay.view.snapshot_key = self.view.snapshot_key;
+        self.output_view = Some(Box::new(overlay));
+    }
+
+    /// Route an event to the output pager while it is open. Esc or q closes
+    /// it and returns to the transcript.
+    fn handle_output_view_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        let Some(output_view) = self.output_view.as_mut() else {
+            return Ok(());
+        };
+        match event {
+            TuiEvent::Key(KeyEvent {
+                code: KeyCode::Esc,
+                kind: KeyEventKind::Press,
+                ..
+            }) if !output_view.view.captures_keys() => output_view.is_done = true,
             TuiEvent::Draw => {
                 tui.draw(u16::MAX, |frame| {
-                    self.render(frame.area(), frame.buffer);
+                    output_view.render(frame.area(), frame.buffer);
                 })?;
-                Ok(())
+                return Ok(());
             }
-            _ => Ok(()),
+            other => output_view.handle_event(tui, other)?,
+        }
+        if output_view.is_done() {
+            self.output_view = None;
+            tui.frame_requester().schedule_frame();
+        }
+        Ok(())
+    }
+
+    /// Toggle a subtle background on every other cell.
+    fn toggle_striping(&mut self) {
+        self.stripe_style = match self.stripe_style {
+            Some(_) => None,
+            None => Some(transcript_stripe_style(default_bg())),
+        };
+        self.rebuild_texts();
+    }
+
+    /// Toggle showing only the assistant's answers.
+    fn toggle_answers_only(&mut self) {
+        self.answers_only = !self.answers_only;
+        self.rebuild_texts();
+    }
+
+    /// Toggle dim directory headers between exec cells run in different
+    /// working directories.
+    fn toggle_group_by_cwd(&mut self) {
+        // Headers shift every row below them, so keep the exec cell at the
+        // top of the viewport in view.
+        let anchor = self
+            .viewport_cells()
+            .into_iter()
+            .find_map(|idx| self.cells[idx].id());
+        self.group_by_cwd = !self.group_by_cwd;
+        self.rebuild_texts();
+        if let Some(id) = anchor {
+            self.reveal_cell_by_id(&id);
         }
     }
-    pub(crate) fn is_done(&self) -> bool {
-        self.is_done
+
+    /// Start replaying the transcript from the first cell, or pause/resume an
+    /// active replay.
+    fn toggle_replay(&mut self) {
+        match self.replay.as_mut() {
+            Some(replay) => {
+                replay.playing = !replay.playing;
+                replay.last_step = Instant::now();
+            }
+            None => {
+                self.replay = Some(ReplayState {
+                    revealed: 0,
+                    playing: true,
+                    last_step: Instant::now(),
+                });
+                self.rebuild_texts();
+            }
+        }
     }
-}
 
-pub(crate) struct StaticOverlay {
-    view: PagerView,
-    is_done: bool,
-}
+    /// Reveal the next cell, entering a paused replay if none is active. The
+    /// replay ends once every cell is visible.
+    fn step_replay(&mut self) {
+        let replay = self.replay.get_or_insert_with(|| ReplayState {
+            revealed: 0,
+            playing: false,
+            last_step: Instant::now(),
+        });
+        replay.revealed += 1;
+        replay.last_step = Instant::now();
+        if replay.revealed >= self.cells.len() {
+            self.replay = None;
+        }
+        self.rebuild_texts();
+        if !self.view.scroll_locked {
+            self.view.scroll_offset = usize::MAX;
+        }
+    }
 
-impl StaticOverlay {
-    pub(crate) fn with_title(lines: Vec<Line<'static>>, title: String) -> Self {
-        Self {
-            view: PagerView::new(vec![Text::from(lines)], title, 0),
-            is_done: false,
+    /// Advance a playing replay when its step interval has elapsed. Returns the
+    /// delay until the next step while the replay keeps playing.
+    fn tick_replay(&mut self) -> Option<Duration> {
+        let replay = self.replay.as_ref().filter(|r| r.playing)?;
+        let elapsed = replay.last_step.elapsed();
+        if elapsed < REPLAY_STEP_INTERVAL {
+            return Some(REPLAY_STEP_INTERVAL - elapsed);
         }
+        self.step_replay();
+        self.replay.as_ref().map(|_| REPLAY_STEP_INTERVAL)
     }
 
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        if self.view.render_transient_footer(area, buf) {
+            return;
+        }
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
         render_key_hints(line1, buf, PAGER_KEY_HINTS);
-        let pairs = [("q", "quit")];
+        let goto = format!("cell 1-{}", self.total_cells());
+        let mut pairs: Vec<(&str, &str)> = vec![
+            ("q", "quit"),
+            ("Esc", "edit prev"),
+            ("/", "search"),
+            ("g", &goto),
+        ];
+        if let Some(idx) = self.highlight_cell {
+            pairs.push(("⏎", "edit message"));
+            if self
+                .cells
+                .get(idx)
+                .is_some_and(|cell| cell.full_transcript_lines().is_some())
+            {
+                pairs.push(("e", "expand"));
+            }
+            if self
+                .cells
+                .get(idx)
+                .is_some_and(|cell| cell.as_any().is::<ExecCell>())
+            {
+                pairs.push(("o", "output"));
+            }
+            if self.failed_command().is_some() {
+                pairs.push(("E", "edit cmd"));
+            }
+        }
+        if let Some(replay) = &self.replay {
+            pairs.push(("Alt+R", if replay.playing { "pause" } else { "play" }));
+            pairs.push(("Alt+.", "step"));
+        }
         render_key_hints(line2, buf, &pairs);
     }
 
@@ -508,14 +2953,73 @@ impl StaticOverlay {
         let top_h = area.height.saturating_sub(3);
         let top = Rect::new(area.x, area.y, area.width, top_h);
         let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
-        self.view.render(top, buf);
+        if self.show_minimap && top.width > 1 {
+            let pager = Rect::new(top.x, top.y, top.width - 1, top.height);
+            self.view.render(pager, buf);
+            let column = Rect::new(pager.right(), top.y, 1, top.height);
+            self.render_minimap(self.view.scroll_area(column), buf);
+        } else {
+            self.view.render(top, buf);
+        }
         self.render_hints(bottom, buf);
     }
+
+    /// Render a one-column overview of the whole transcript: a marker where
+    /// each cell starts and a reversed band covering the current viewport.
+    fn render_minimap(&self, area: Rect, buf: &mut Buffer) {
+        let Some(cache) = self.view.wrap_cache.as_ref() else {
+            return;
+        };
+        let total = cache.wrapped.len();
+        let rows = area.height as usize;
+        if total == 0 || rows == 0 {
+            return;
+        }
+        let row_for = |line: usize| (line * rows / total).min(rows - 1);
+        Clear.render(area, buf);
+
+        let mut column = vec![" "; rows];
+        for (cell, range) in self.visible_cells().iter().zip(&cache.chunk_ranges) {
+            if !cell.is_stream_continuation() && !range.is_empty() {
+                column[row_for(range.start)] = "•";
+            }
+        }
+
+        let height = self.view.last_content_height.unwrap_or(rows);
+        let start = self.view.scroll_offset.min(total.saturating_sub(1));
+        let end = (start + height).min(total);
+        let band = row_for(start)..=row_for(end.saturating_sub(1));
+        for (row, symbol) in column.into_iter().enumerate() {
+            let rect = Rect::new(area.x, area.y + row as u16, 1, 1);
+            if band.contains(&row) {
+                symbol.reversed().render_ref(rect, buf);
+            } else {
+                symbol.dim().render_ref(rect, buf);
+            }
+        }
+    }
 }
 
-impl StaticOverlay {
+impl TranscriptOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        if self.output_view.is_some() {
+            return self.handle_output_view_event(tui, event);
+        }
+        if let TuiEvent::Key(key_event) = event
+            && !self.view.captures_keys()
+            && self.handle_goto_key(key_event)
+        {
+            tui.frame_requester().schedule_frame();
+            return Ok(());
+        }
         match event {
+            // A pending mark letter or the search prompt claims the key.
+            TuiEvent::Key(key_event)
+                if self.view.captures_keys()
+                    && !key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                self.view.handle_key_event(tui, key_event)
+            }
             TuiEvent::Key(key_event) => match key_event {
                 KeyEvent {
                     code: KeyCode::Char('q'),
@@ -523,20 +3027,259 @@ impl StaticOverlay {
                     ..
                 }
                 | KeyEvent {
-                    code: KeyCode::Char('c'),
+                    code: KeyCode::Char('t'),
                     modifiers: crossterm::event::KeyModifiers::CONTROL,
                     kind: KeyEventKind::Press,
                     ..
-                } => {
+                }
+                | KeyEvent {
+                    code: KeyCode::Char('c'),
+                    modifiers: crossterm::event::KeyModifiers::CONTROL,
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.is_done = true;
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('r'),
+                    modifiers: KeyModifiers::ALT,
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.toggle_replay();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('.'),
+                    modifiers: KeyModifiers::ALT,
+                    kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                    ..
+                } => {
+                    self.step_replay();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('M'),
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.show_minimap = !self.show_minimap;
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('e'),
+                    modifiers: KeyModifiers::NONE,
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.toggle_expand_highlighted();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('Y'),
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.copy_command_with_output();
+                    self.view
+                        .schedule_transient_footer_frames(&tui.frame_requester());
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('o'),
+                    modifiers: KeyModifiers::NONE,
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.open_output_view();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('e'),
+                    modifiers: KeyModifiers::CONTROL,
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.export_markdown_file();
+                    self.view
+                        .schedule_transient_footer_frames(&tui.frame_requester());
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('E'),
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.edit_failed_command();
+                    self.view
+                        .schedule_transient_footer_frames(&tui.frame_requester());
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('f'),
+                    modifiers: KeyModifiers::NONE,
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.open_cwd();
+                    self.view
+                        .schedule_transient_footer_frames(&tui.frame_requester());
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('z'),
+                    modifiers: KeyModifiers::NONE,
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.toggle_striping();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('A'),
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.toggle_answers_only();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: KeyCode::Char('C'),
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
+                    self.toggle_group_by_cwd();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: code @ (KeyCode::Char('n') | KeyCode::Char('p')),
+                    modifiers: KeyModifiers::CONTROL,
+                    kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                    ..
+                } => {
+                    self.step_diagnostic(code == KeyCode::Char('n'));
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                KeyEvent {
+                    code: code @ (KeyCode::Char(']') | KeyCode::Char('[')),
+                    kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                    ..
+                } => {
+                    self.step_search_hit(code == KeyCode::Char(']'));
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                other => self.view.handle_key_event(tui, other),
+            },
+            TuiEvent::Mouse(mouse_event) => {
+                self.view.handle_mouse_event(tui, mouse_event);
+                Ok(())
+            }
+            TuiEvent::Draw => {
+                if let Some(delay) = self.tick_replay() {
+                    tui.frame_requester().schedule_frame_in(delay);
+                }
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                if self.view.search_in_progress() {
+                    tui.frame_requester().schedule_frame();
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
+pub(crate) struct StaticOverlay {
+    view: PagerView,
+    is_done: bool,
+}
+
+impl StaticOverlay {
+    pub(crate) fn with_title(lines: Vec<Line<'static>>, title: String) -> Self {
+        Self {
+            view: PagerView::new(vec![Text::from(lines)], title, 0),
+            is_done: false,
+        }
+    }
+
+    fn debug_snapshot(&self) -> String {
+        format!("overlay: static\n{}", self.view.debug_snapshot())
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        if self.view.render_transient_footer(area, buf) {
+            return;
+        }
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        let pairs = [("q", "quit"), ("m/'", "mark"), ("c", "position")];
+        render_key_hints(line2, buf, &pairs);
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let top_h = area.height.saturating_sub(3);
+        let top = Rect::new(area.x, area.y, area.width, top_h);
+        let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        self.view.render(top, buf);
+        self.render_hints(bottom, buf);
+    }
+}
+
+impl StaticOverlay {
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            // A pending mark letter or the search prompt claims the key.
+            TuiEvent::Key(key_event)
+                if self.view.captures_keys()
+                    && !key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                self.view.handle_key_event(tui, key_event)
+            }
+            TuiEvent::Key(key_event) => match key_event {
+                KeyEvent {
+                    code: KeyCode::Char('q'),
+                    kind: KeyEventKind::Press,
+                    ..
+                }
+                | KeyEvent {
+                    code: KeyCode::Char('c'),
+                    modifiers: crossterm::event::KeyModifiers::CONTROL,
+                    kind: KeyEventKind::Press,
+                    ..
+                } => {
                     self.is_done = true;
                     Ok(())
                 }
                 other => self.view.handle_key_event(tui, other),
             },
+            TuiEvent::Mouse(mouse_event) => {
+                self.view.handle_mouse_event(tui, mouse_event);
+                Ok(())
+            }
             TuiEvent::Draw => {
                 tui.draw(u16::MAX, |frame| {
                     self.render(frame.area(), frame.buffer);
                 })?;
+                if self.view.search_in_progress() {
+                    tui.frame_requester().schedule_frame();
+                }
                 Ok(())
             }
             _ => Ok(()),
@@ -564,6 +3307,7 @@ mod tests {
     use codex_protocol::parse_command::ParsedCommand;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
+    use tokio::sync::mpsc::unbounded_channel;
 
     #[derive(Debug)]
     struct TestCell {
@@ -580,206 +3324,1958 @@ mod tests {
         }
     }
 
+    #[derive(Debug)]
+    struct TruncatedCell {
+        summary: Vec<Line<'static>>,
+        full: Vec<Line<'static>>,
+    }
+
+    impl crate::history_cell::HistoryCell for TruncatedCell {
+        fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+            self.summary.clone()
+        }
+
+        fn full_transcript_lines(&self) -> Option<Vec<Line<'static>>> {
+            Some(self.full.clone())
+        }
+    }
+
+    #[test]
+    fn transcript_expand_toggles_full_transcript_lines() {
+        let mut overlay = TranscriptOverlay::new(vec![Arc::new(TruncatedCell {
+            summary: vec![Line::from("line 1"), Line::from("…")],
+            full: (1..=4).map(|i| Line::from(format!("line {i}"))).collect(),
+        })]);
+        let line_count = |overlay: &TranscriptOverlay| -> usize {
+            overlay.view.texts.iter().map(|t| t.lines.len()).sum()
+        };
+        assert_eq!(line_count(&overlay), 2);
+
+        // Without a highlighted cell there is nothing to expand.
+        overlay.toggle_expand_highlighted();
+        assert_eq!(line_count(&overlay), 2);
+
+        overlay.set_highlight_cell(Some(0));
+        overlay.toggle_expand_highlighted();
+        assert_eq!(line_count(&overlay), 4);
+
+        overlay.toggle_expand_highlighted();
+        assert_eq!(line_count(&overlay), 2);
+    }
+
+    #[test]
+    fn edit_prev_hint_is_visible() {
+        let mut overlay = TranscriptOverlay::new(vec![Arc::new(TestCell {
+            lines: vec![Line::from("hello")],
+        })]);
+
+        // Render into a small buffer and assert the backtrack hint is present
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+
+        // Flatten buffer to a string and check for the hint text
+        let mut s = String::new();
+        for y in area.y..area.bottom() {
+            for x in area.x..area.right() {
+                s.push(buf[(x, y)].symbol().chars().next().unwrap_or(' '));
+            }
+            s.push('\n');
+        }
+        assert!(
+            s.contains("edit prev"),
+            "expected 'edit prev' hint in overlay footer, got: {s:?}"
+        );
+    }
+
+    fn buffer_text(buf: &Buffer) -> String {
+        let area = buf.area;
+        let mut s = String::new();
+        for y in area.y..area.bottom() {
+            for x in area.x..area.right() {
+                s.push(buf[(x, y)].symbol().chars().next().unwrap_or(' '));
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    #[test]
+    fn overlays_render_into_provided_buffer() {
+        let area = Rect::new(0, 0, 40, 10);
+
+        let mut transcript = Overlay::new_transcript(vec![Arc::new(TestCell {
+            lines: vec![Line::from("hello transcript")],
+        })]);
+        let mut buf = Buffer::empty(area);
+        transcript.render_into(area, &mut buf);
+        let text = buffer_text(&buf);
+        assert!(text.contains("T R A N S C R I P T"), "got: {text:?}");
+        assert!(text.contains("hello transcript"), "got: {text:?}");
+
+        let mut static_overlay =
+            Overlay::new_static_with_title(vec!["hello static".into()], "D I F F".to_string());
+        let mut buf = Buffer::empty(area);
+        static_overlay.render_into(area, &mut buf);
+        let text = buffer_text(&buf);
+        assert!(text.contains("D I F F"), "got: {text:?}");
+        assert!(text.contains("hello static"), "got: {text:?}");
+    }
+
+    #[test]
+    fn transcript_overlay_snapshot_basic() {
+        // Prepare a transcript overlay with a few lines
+        let mut overlay = TranscriptOverlay::new(vec![
+            Arc::new(TestCell {
+                lines: vec![Line::from("alpha")],
+            }),
+            Arc::new(TestCell {
+                lines: vec![Line::from("beta")],
+            }),
+            Arc::new(TestCell {
+                lines: vec![Line::from("gamma")],
+            }),
+        ]);
+        let mut term = Terminal::new(TestBackend::new(40, 10)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        assert_snapshot!(term.backend());
+    }
+
+    fn buffer_to_text(buf: &Buffer, area: Rect) -> String {
+        let mut out = String::new();
+        for y in area.y..area.bottom() {
+            for x in area.x..area.right() {
+                let symbol = buf[(x, y)].symbol();
+                if symbol.is_empty() {
+                    out.push(' ');
+                } else {
+                    out.push(symbol.chars().next().unwrap_or(' '));
+                }
+            }
+            // Trim trailing spaces for stability.
+            while out.ends_with(' ') {
+                out.pop();
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    #[test]
+    fn transcript_overlay_apply_patch_scroll_vt100_clears_previous_page() {
+        let cwd = PathBuf::from("/repo");
+        let mut cells: Vec<Arc<dyn HistoryCell>> = Vec::new();
+
+        let mut approval_changes = HashMap::new();
+        approval_changes.insert(
+            PathBuf::from("foo.txt"),
+            FileChange::Add {
+                content: "hello\nworld\n".to_string(),
+            },
+        );
+        let approval_cell: Arc<dyn HistoryCell> = Arc::new(new_patch_event(
+            PatchEventType::ApprovalRequest,
+            approval_changes,
+            &cwd,
+        ));
+        cells.push(approval_cell);
+
+        let mut apply_changes = HashMap::new();
+        apply_changes.insert(
+            PathBuf::from("foo.txt"),
+            FileChange::Add {
+                content: "hello\nworld\n".to_string(),
+            },
+        );
+        let apply_begin_cell: Arc<dyn HistoryCell> = Arc::new(new_patch_event(
+            PatchEventType::ApplyBegin {
+                auto_approved: false,
+            },
+            apply_changes,
+            &cwd,
+        ));
+        cells.push(apply_begin_cell);
+
+        let apply_end_cell: Arc<dyn HistoryCell> =
+            Arc::new(crate::history_cell::new_user_approval_decision(vec![
+                "✓ Patch applied".green().bold().into(),
+                "src/foo.txt".dim().into(),
+            ]));
+        cells.push(apply_end_cell);
+
+        let mut exec_cell = crate::exec_cell::new_active_exec_command(
+            "exec-1".into(),
+            vec!["bash".into(), "-lc".into(), "ls".into()],
+            vec![ParsedCommand::Unknown { cmd: "ls".into() }],
+        );
+        exec_cell.complete_call(
+            "exec-1",
+            CommandOutput {
+                exit_code: 0,
+                stdout: "src\nREADME.md\n".into(),
+                stderr: String::new(),
+                formatted_output: "src\nREADME.md\n".into(),
+                diagnostics: Vec::new(),
+            },
+            Duration::from_millis(420),
+        );
+        let exec_cell: Arc<dyn HistoryCell> = Arc::new(exec_cell);
+        cells.push(exec_cell);
+
+        let mut overlay = TranscriptOverlay::new(cells);
+        let area = Rect::new(0, 0, 80, 12);
+        let mut buf = Buffer::empty(area);
+
+        overlay.render(area, &mut buf);
+        overlay.view.scroll_offset = 0;
+        overlay.view.wrap_cache = None;
+        overlay.render(area, &mut buf);
+
+        let snapshot = buffer_to_text(&buf, area);
+        assert_snapshot!("transcript_overlay_apply_patch_scroll_vt100", snapshot);
+    }
+
+    #[test]
+    fn transcript_overlay_keeps_scroll_pinned_at_bottom() {
+        let mut overlay = TranscriptOverlay::new(
+            (0..20)
+                .map(|i| {
+                    Arc::new(TestCell {
+                        lines: vec![Line::from(format!("line{i}"))],
+                    }) as Arc<dyn HistoryCell>
+                })
+                .collect(),
+        );
+        let mut term = Terminal::new(TestBackend::new(40, 12)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+
+        assert!(
+            overlay.view.is_scrolled_to_bottom(),
+            "expected initial render to leave view at bottom"
+        );
+
+        overlay.insert_cells(vec![Arc::new(TestCell {
+            lines: vec!["tail".into()],
+        })]);
+
+        assert_eq!(overlay.view.scroll_offset, usize::MAX);
+    }
+
+    #[test]
+    fn transcript_insert_cells_appends_batch_and_keeps_following() {
+        let mut overlay = TranscriptOverlay::new(vec![Arc::new(TestCell {
+            lines: vec![Line::from("first")],
+        })]);
+        let mut term = Terminal::new(TestBackend::new(40, 12)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+
+        overlay.insert_cells(
+            (0..3)
+                .map(|i| {
+                    Arc::new(TestCell {
+                        lines: vec![Line::from(format!("chunk{i}"))],
+                    }) as Arc<dyn HistoryCell>
+                })
+                .collect(),
+        );
+        assert_eq!(overlay.view.texts.len(), 4);
+        assert!(overlay.view.wrap_cache.is_none());
+        assert_eq!(overlay.view.scroll_offset, usize::MAX);
+
+        // One render rebuilds the cache for the whole batch.
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        let cache = overlay.view.wrap_cache.as_ref().expect("wrap cache");
+        assert_eq!(cache.base_len, 4);
+        assert_eq!(cache.chunk_ranges.len(), 4);
+    }
+
+    fn follow_overlay(follow: TranscriptFollow) -> TranscriptOverlay {
+        let mut overlay = TranscriptOverlay::new(
+            (0..20)
+                .map(|i| {
+                    Arc::new(TestCell {
+                        lines: vec![Line::from(format!("line{i}"))],
+                    }) as Arc<dyn HistoryCell>
+                })
+                .collect(),
+        );
+        overlay.follow = follow;
+        let mut term = Terminal::new(TestBackend::new(40, 12)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        overlay
+    }
+
+    fn push_tail(overlay: &mut TranscriptOverlay) {
+        overlay.insert_cells(vec![Arc::new(TestCell {
+            lines: vec!["tail".into()],
+        })]);
+    }
+
+    #[test]
+    fn transcript_follow_when_at_bottom_keeps_manual_scroll() {
+        let mut overlay = follow_overlay(TranscriptFollow::FollowWhenAtBottom);
+        overlay.view.scroll_offset = 3;
+        push_tail(&mut overlay);
+        assert_eq!(overlay.view.scroll_offset, 3);
+    }
+
+    #[test]
+    fn transcript_always_jump_scrolls_to_new_output() {
+        let mut overlay = follow_overlay(TranscriptFollow::AlwaysJump);
+        overlay.view.scroll_offset = 3;
+        push_tail(&mut overlay);
+        assert_eq!(overlay.view.scroll_offset, usize::MAX);
+    }
+
+    #[test]
+    fn transcript_scroll_lock_overrides_follow() {
+        let mut overlay = follow_overlay(TranscriptFollow::AlwaysJump);
+        let bottom = overlay.view.effective_scroll_offset();
+        overlay.view.toggle_scroll_lock();
+        assert_eq!(overlay.view.scroll_offset, bottom);
+
+        push_tail(&mut overlay);
+        push_tail(&mut overlay);
+        assert_eq!(overlay.view.scroll_offset, bottom);
+        assert!(!overlay.view.should_follow_bottom());
+
+        overlay.view.toggle_scroll_lock();
+        push_tail(&mut overlay);
+        assert_eq!(overlay.view.scroll_offset, usize::MAX);
+    }
+
+    #[test]
+    fn transcript_push_notice_appends_styled_cell_and_follows() {
+        let mut overlay = follow_overlay(TranscriptFollow::FollowWhenAtBottom);
+        let before = overlay.cells.len();
+        overlay.push_notice("rate limited, retrying", NoticeStyle::Warning);
+
+        assert_eq!(overlay.cells.len(), before + 1);
+        assert_eq!(overlay.view.scroll_offset, usize::MAX);
+        let last = overlay.view.texts.last().expect("notice chunk");
+        let line = last.lines.last().expect("notice line");
+        let span = &line.spans[0];
+        assert_eq!(span.content, "⚠ rate limited, retrying");
+        assert_eq!(span.style.fg, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn transcript_repeated_notice_collapses_into_a_counter() {
+        let mut overlay = follow_overlay(TranscriptFollow::FollowWhenAtBottom);
+        let before = overlay.cells.len();
+        for _ in 0..3 {
+            overlay.push_notice("rate limited, retrying", NoticeStyle::Warning);
+        }
+
+        assert_eq!(overlay.cells.len(), before + 1);
+        assert_eq!(overlay.view.texts.len(), overlay.cells.len());
+        let line = overlay.view.texts.last().and_then(|t| t.lines.last());
+        let span = &line.expect("notice line").spans[0];
+        assert_eq!(span.content, "⚠ rate limited, retrying (×3)");
+
+        // A different notice, or any cell in between, starts a fresh count.
+        overlay.push_notice("rate limited, retrying", NoticeStyle::Error);
+        push_tail(&mut overlay);
+        overlay.push_notice("rate limited, retrying", NoticeStyle::Error);
+        assert_eq!(overlay.cells.len(), before + 4);
+        let line = overlay.view.texts.last().and_then(|t| t.lines.last());
+        assert_eq!(
+            line.expect("notice line").spans[0].content,
+            "■ rate limited, retrying"
+        );
+    }
+
+    #[test]
+    fn transcript_goto_jumps_to_numbered_first_and_last_cells() {
+        let mut overlay = follow_overlay(TranscriptFollow::FollowWhenAtBottom);
+        let press = |overlay: &mut TranscriptOverlay, code: KeyCode| {
+            overlay.handle_goto_key(KeyEvent::new(code, KeyModifiers::NONE))
+        };
+
+        for code in [KeyCode::Char('g'), KeyCode::Char('1'), KeyCode::Char('2')] {
+            assert!(press(&mut overlay, code));
+        }
+        assert_eq!(overlay.view.footer_note.as_deref(), Some("go to cell 12"));
+        assert!(press(&mut overlay, KeyCode::Enter));
+        assert_eq!(overlay.view.pending_scroll_chunk, Some(11));
+        assert_eq!(overlay.pending_goto, None);
+
+        assert!(press(&mut overlay, KeyCode::Char('g')));
+        assert!(press(&mut overlay, KeyCode::Char('g')));
+        assert_eq!(overlay.view.pending_scroll_chunk, Some(0));
+
+        assert!(press(&mut overlay, KeyCode::Char('G')));
+        assert_eq!(overlay.view.pending_scroll_chunk, Some(19));
+
+        // Out-of-range numbers clamp to the last cell; Esc just cancels.
+        overlay.view.pending_scroll_chunk = None;
+        for code in [KeyCode::Char('g'), KeyCode::Char('9'), KeyCode::Char('9')] {
+            press(&mut overlay, code);
+        }
+        press(&mut overlay, KeyCode::Esc);
+        assert_eq!(overlay.view.pending_scroll_chunk, None);
+        for code in [KeyCode::Char('g'), KeyCode::Char('9'), KeyCode::Char('9')] {
+            press(&mut overlay, code);
+        }
+        press(&mut overlay, KeyCode::Enter);
+        assert_eq!(overlay.view.pending_scroll_chunk, Some(19));
+        assert!(!press(&mut overlay, KeyCode::Char('x')));
+    }
+
+    #[test]
+    fn transcript_truncates_lines_past_max_line_chars() {
+        let huge = "x".repeat(100_000);
+        let overlay = match Overlay::new_transcript(vec![Arc::new(TestCell {
+            lines: vec![Line::from(vec!["$ ".red(), huge.into()]), "short".into()],
+        })])
+        .with_max_line_chars(500)
+        {
+            Overlay::Transcript(o) => o,
+            Overlay::Static(_) => unreachable!("expected transcript overlay"),
+        };
+
+        let lines = &overlay.view.texts[0].lines;
+        let spans = &lines[0].spans;
+        assert_eq!(spans[0].content, "$ ");
+        assert_eq!(spans[0].style.fg, Some(Color::Red));
+        assert_eq!(spans[1].content.len(), 498);
+        assert_eq!(spans[2].content, "…(+99502 chars)");
+        assert!(spans[2].style.add_modifier.contains(Modifier::DIM));
+        assert_eq!(lines[1].spans[0].content, "short");
+    }
+
+    #[test]
+    fn expanding_a_cell_shows_its_long_lines_untruncated() {
+        let huge = "x".repeat(1_000);
+        let mut overlay = match Overlay::new_transcript(vec![Arc::new(TruncatedCell {
+            summary: vec![Line::from(huge.clone())],
+            full: vec![Line::from(huge.clone())],
+        })])
+        .with_max_line_chars(500)
+        {
+            Overlay::Transcript(o) => o,
+            Overlay::Static(_) => unreachable!("expected transcript overlay"),
+        };
+        let first_line = |overlay: &TranscriptOverlay| -> String {
+            overlay.view.texts[0].lines[0]
+                .spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        };
+        assert!(first_line(&overlay).ends_with("…(+500 chars)"));
+
+        overlay.set_highlight_cell(Some(0));
+        overlay.toggle_expand_highlighted();
+        assert_eq!(first_line(&overlay), huge);
+    }
+
+    #[test]
+    fn transcript_from_response_items_renders_placeholder_for_unknown() {
+        let overlay = TranscriptOverlay::from_response_items(vec![ResponseItem::Other]);
+        assert_eq!(overlay.cells.len(), 1);
+        let line = &overlay.view.texts[0].lines[0];
+        assert_eq!(line.spans[0].content, "[unsupported item not shown]");
+        assert!(line.spans[0].style.add_modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn transcript_never_jump_stays_put_even_at_bottom() {
+        let mut overlay = follow_overlay(TranscriptFollow::NeverJump);
+        overlay.view.scroll_offset = 3;
+        push_tail(&mut overlay);
+        assert_eq!(overlay.view.scroll_offset, 3);
+
+        // Sitting at the bottom pins the concrete offset instead of following.
+        overlay.view.scroll_offset = usize::MAX;
+        let bottom = overlay.view.effective_scroll_offset();
+        push_tail(&mut overlay);
+        assert_eq!(overlay.view.scroll_offset, bottom);
+    }
+
+    #[test]
+    fn transcript_content_hash_tracks_cell_text() {
+        let cells = |texts: &[&str]| -> Vec<Arc<dyn HistoryCell>> {
+            texts
+                .iter()
+                .map(|text| {
+                    Arc::new(TestCell {
+                        lines: vec![Line::from(text.to_string())],
+                    }) as Arc<dyn HistoryCell>
+                })
+                .collect()
+        };
+        let a = TranscriptOverlay::new(cells(&["alpha", "beta", "gamma"]));
+        let b = TranscriptOverlay::new(cells(&["alpha", "beta", "gamma"]));
+        let changed = TranscriptOverlay::new(cells(&["alpha", "BETA", "gamma"]));
+        let reordered = TranscriptOverlay::new(cells(&["beta", "alpha", "gamma"]));
+
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_ne!(a.content_hash(), changed.content_hash());
+        assert_ne!(a.content_hash(), reordered.content_hash());
+    }
+
+    #[derive(Debug)]
+    struct ContinuationCell;
+
+    impl crate::history_cell::HistoryCell for ContinuationCell {
+        fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+            vec![Line::from("continued")]
+        }
+
+        fn is_stream_continuation(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn transcript_highlight_rules_color_matches_through_wrapping() {
+        let mut overlay = TranscriptOverlay::new(vec![Arc::new(TestCell {
+            lines: vec![Line::from("build ERROR in ABC-123")],
+        })]);
+        let rules = compile_highlight_rules([
+            ("ERROR", Style::default().fg(Color::Red)),
+            (r"[A-Z]+-\d+", Style::default().fg(Color::Yellow)),
+        ])
+        .expect("valid patterns");
+        overlay.set_highlight_rules(rules);
+
+        fn styled_text(lines: &[Line<'_>], color: Color) -> String {
+            lines
+                .iter()
+                .flat_map(|line| &line.spans)
+                .filter(|span| span.style.fg == Some(color))
+                .map(|span| span.content.as_ref())
+                .collect()
+        }
+        let line = overlay.view.texts[0].lines[0].clone();
+        assert_eq!(
+            styled_text(std::slice::from_ref(&line), Color::Red),
+            "ERROR"
+        );
+        assert_eq!(
+            styled_text(std::slice::from_ref(&line), Color::Yellow),
+            "ABC-123"
+        );
+
+        // Wrapping runs after styling and must keep the colored spans.
+        let wrapped = crate::wrapping::word_wrap_line(&line, 8);
+        assert!(wrapped.len() > 1, "expected the line to wrap");
+        assert_eq!(styled_text(&wrapped, Color::Red), "ERROR");
+        assert_eq!(styled_text(&wrapped, Color::Yellow), "ABC-123");
+
+        // The selected-cell highlight layers on top of the rule colors.
+        overlay.set_highlight_cell(Some(0));
+        let error = overlay.view.texts[0].lines[0]
+            .spans
+            .iter()
+            .find(|span| span.content == "ERROR")
+            .expect("ERROR span");
+        assert_eq!(error.style.fg, Some(Color::Red));
+        assert!(
+            error
+                .style
+                .add_modifier
+                .union(overlay.view.texts[0].lines[0].style.add_modifier)
+                .contains(Modifier::REVERSED)
+        );
+    }
+
+    #[test]
+    fn compile_highlight_rules_reports_invalid_patterns() {
+        let err =
+            compile_highlight_rules([("WARN", Style::default()), ("(unclosed", Style::default())])
+                .expect_err("invalid pattern");
+        assert!(err.contains("`(unclosed`"), "got: {err}");
+    }
+
+    #[test]
+    fn transcript_highlights_from_config_skip_invalid_entries() {
+        let cell = || -> Vec<Arc<dyn HistoryCell>> {
+            vec![Arc::new(TestCell {
+                lines: vec![Line::from("WARN: disk low")],
+            })]
+        };
+        let highlight = |pattern: &str, color: &str| TranscriptHighlight {
+            pattern: pattern.to_string(),
+            color: color.to_string(),
+        };
+        let warn_style = |overlay: &Overlay| match overlay {
+            Overlay::Transcript(o) => o.view.texts[0].lines[0]
+                .spans
+                .iter()
+                .find(|span| span.content == "WARN")
+                .map(|span| span.style.fg),
+            Overlay::Static(_) => unreachable!(),
+        };
+
+        let overlay =
+            Overlay::new_transcript(cell()).with_highlights(&[highlight("WARN", "#ffaf00")]);
+        assert_eq!(
+            warn_style(&overlay),
+            Some(Some(Color::Rgb(0xff, 0xaf, 0x00)))
+        );
+
+        for bad in [highlight("WARN", "not-a-color"), highlight("(WARN", "red")] {
+            let overlay = Overlay::new_transcript(cell()).with_highlights(&[bad]);
+            assert_eq!(warn_style(&overlay), None);
+        }
+    }
+
+    #[test]
+    fn transcript_striping_alternates_by_cell() {
+        let cell = |text: &str| -> Arc<dyn HistoryCell> {
+            Arc::new(TestCell {
+                lines: vec![Line::from(text.to_string())],
+            })
+        };
+        let mut overlay = TranscriptOverlay::new(vec![
+            cell("one"),
+            cell("two"),
+            Arc::new(ContinuationCell),
+            cell("three"),
+        ]);
+        let stripe = Style::default().bg(Color::DarkGray);
+        overlay.stripe_style = Some(stripe);
+        overlay.rebuild_texts();
+
+        let last_bg = |idx: usize| {
+            overlay.view.texts[idx]
+                .lines
+                .last()
+                .and_then(|l| l.style.bg)
+        };
+        assert_eq!(last_bg(0), None);
+        assert_eq!(last_bg(1), stripe.bg);
+        // The continuation keeps the stripe of "two" rather than flipping it.
+        assert_eq!(last_bg(2), stripe.bg);
+        assert_eq!(last_bg(3), None);
+    }
+
+    #[test]
+    fn transcript_drops_oldest_cells_beyond_cap() {
+        let cell = |i: usize| -> Arc<dyn HistoryCell> {
+            Arc::new(TestCell {
+                lines: vec![Line::from(format!("cell{i}"))],
+            })
+        };
+        let mut overlay =
+            match Overlay::new_transcript((0..3).map(cell).collect()).with_max_cells(Some(3)) {
+                Overlay::Transcript(o) => o,
+                Overlay::Static(_) => unreachable!("expected transcript overlay"),
+            };
+        assert_eq!(overlay.hidden_cells, 0);
+
+        overlay.insert_cells((3..5).map(cell).collect());
+
+        let plain: Vec<String> = overlay
+            .view
+            .texts
+            .iter()
+            .flat_map(|text| text.lines.iter())
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .filter(|line: &String| !line.is_empty())
+            .collect();
+        assert_eq!(
+            plain,
+            vec!["… 2 earlier cells hidden", "cell2", "cell3", "cell4"]
+        );
+        assert_eq!(overlay.cells.len(), 4);
+
+        // Full-transcript indices still reach kept cells; dropped ones do not.
+        overlay.set_highlight_cell(Some(3));
+        assert_eq!(overlay.highlight_cell, Some(2));
+        overlay.set_highlight_cell(Some(1));
+        assert_eq!(overlay.highlight_cell, None);
+    }
+
+    #[derive(Debug)]
+    struct IdCell {
+        id: &'static str,
+    }
+
+    impl crate::history_cell::HistoryCell for IdCell {
+        fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+            (0..4)
+                .map(|i| Line::from(format!("{} {i}", self.id)))
+                .collect()
+        }
+
+        fn id(&self) -> Option<CellId> {
+            Some(CellId(self.id.to_string()))
+        }
+    }
+
+    #[test]
+    fn transcript_reveals_cell_by_id_after_earlier_cells_drop() {
+        let cell = |id: &'static str| -> Arc<dyn HistoryCell> { Arc::new(IdCell { id }) };
+        let mut overlay = match Overlay::new_transcript(vec![cell("a"), cell("b"), cell("c")])
+            .with_max_cells(Some(3))
+        {
+            Overlay::Transcript(o) => o,
+            Overlay::Static(_) => unreachable!("expected transcript overlay"),
+        };
+        overlay.insert_cells(vec![cell("d"), cell("e")]);
+        // "a" and "b" collapsed into the marker, so "c" moved from 2 to 1.
+        assert_eq!(overlay.hidden_cells, 2);
+
+        assert!(!overlay.reveal_cell_by_id(&CellId("a".to_string())));
+        assert!(overlay.reveal_cell_by_id(&CellId("c".to_string())));
+        assert_eq!(overlay.view.pending_scroll_chunk, Some(1));
+
+        overlay.view.scroll_offset = usize::MAX;
+        let area = Rect::new(0, 0, 20, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        assert!(buffer_text(&buf).contains("c 0"), "{}", buffer_text(&buf));
+    }
+
+    #[test]
+    fn transcript_cap_keeps_manual_scroll_on_same_content() {
+        let cell = |i: usize| -> Arc<dyn HistoryCell> {
+            Arc::new(TestCell {
+                lines: vec![Line::from(format!("cell{i}"))],
+            })
+        };
+        let mut overlay = TranscriptOverlay::new((0..20).map(cell).collect());
+        overlay.max_cells = Some(19);
+        let mut term = Terminal::new(TestBackend::new(40, 12)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        // Each cell after the first is a blank separator plus its line, so
+        // row 20 is the line of cell10.
+        overlay.view.scroll_offset = 20;
+
+        overlay.insert_cells(vec![cell(20)]);
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+
+        let top = overlay.view.cached()[overlay.view.scroll_offset..]
+            .iter()
+            .find(|line| !line.spans.is_empty())
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|s| s.content.as_ref())
+                    .collect::<String>()
+            });
+        assert_eq!(top.as_deref(), Some("cell10"));
+    }
+
+    #[test]
+    fn transcript_overlay_preserves_manual_scroll_position() {
+        let mut overlay = TranscriptOverlay::new(
+            (0..20)
+                .map(|i| {
+                    Arc::new(TestCell {
+                        lines: vec![Line::from(format!("line{i}"))],
+                    }) as Arc<dyn HistoryCell>
+                })
+                .collect(),
+        );
+        let mut term = Terminal::new(TestBackend::new(40, 12)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+
+        overlay.view.scroll_offset = 0;
+
+        overlay.insert_cells(vec![Arc::new(TestCell {
+            lines: vec!["tail".into()],
+        })]);
+
+        assert_eq!(overlay.view.scroll_offset, 0);
+    }
+
+    #[test]
+    fn transcript_replay_step_reveals_first_cell_only() {
+        let mut overlay = TranscriptOverlay::new(vec![
+            Arc::new(TestCell {
+                lines: vec![Line::from("alpha")],
+            }),
+            Arc::new(TestCell {
+                lines: vec![Line::from("beta")],
+            }),
+            Arc::new(TestCell {
+                lines: vec![Line::from("gamma")],
+            }),
+        ]);
+
+        overlay.step_replay();
+
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+        assert!(text.contains("alpha"), "expected first cell: {text:?}");
+        assert!(
+            !text.contains("beta"),
+            "second cell revealed early: {text:?}"
+        );
+        assert!(
+            !text.contains("gamma"),
+            "third cell revealed early: {text:?}"
+        );
+
+        overlay.step_replay();
+        overlay.step_replay();
+        assert!(overlay.replay.is_none(), "replay should end at last cell");
+        overlay.render(area, &mut buf);
+        assert!(buffer_to_text(&buf, area).contains("gamma"));
+    }
+
+    #[test]
+    fn question_mark_opens_a_help_screen_listing_transcript_keys() {
+        let mut overlay = TranscriptOverlay::new(vec![Arc::new(TestCell {
+            lines: vec![Line::from("alpha")],
+        })]);
+        let area = Rect::new(0, 0, 60, 60);
+        let mut buf = Buffer::empty(area);
+
+        assert!(
+            overlay
+                .view
+                .apply_key_event(press(KeyCode::Char('?')), area)
+        );
+        assert!(overlay.view.captures_keys());
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+        assert!(text.contains("K E Y S"), "{text}");
+        assert!(text.contains("scroll one line"), "{text}");
+        assert!(text.contains("Alt+R"), "{text}");
+        assert!(text.contains("F12"), "{text}");
+        assert!(!text.contains("alpha"), "{text}");
+
+        // `q` closes the help rather than the transcript.
+        assert!(
+            overlay
+                .view
+                .apply_key_event(press(KeyCode::Char('q')), area)
+        );
+        assert!(!overlay.view.captures_keys());
+        overlay.render(area, &mut buf);
+        assert!(buffer_to_text(&buf, area).contains("alpha"));
+    }
+
+    #[test]
+    fn transcript_minimap_marks_cells_and_viewport_band() {
+        let mut overlay = TranscriptOverlay::new(
+            (0..20)
+                .map(|i| {
+                    Arc::new(TestCell {
+                        lines: vec![Line::from(format!("line{i}"))],
+                    }) as Arc<dyn HistoryCell>
+                })
+                .collect(),
+        );
+        overlay.show_minimap = true;
+        overlay.view.scroll_offset = 0;
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+
+        // Content rows are 1..=7; the minimap lives in the last column.
+        let x = area.right() - 1;
+        let reversed = |buf: &Buffer, y: u16| {
+            buf[(x, y)]
+                .style()
+                .add_modifier
+                .contains(ratatui::style::Modifier::REVERSED)
+        };
+        assert_eq!(buf[(x, 1)].symbol(), "•");
+        assert!((1..=7).any(|y| buf[(x, y)].symbol() == "•" && y > 1));
+        assert!(reversed(&buf, 1), "viewport band should start at the top");
+        assert!(!reversed(&buf, 7), "band should not cover the bottom");
+
+        overlay.view.scroll_offset = usize::MAX;
+        overlay.render(area, &mut buf);
+        assert!(!reversed(&buf, 1), "band should leave the top");
+        assert!(reversed(&buf, 7), "band should reach the bottom");
+    }
+
+    #[test]
+    fn transcript_jump_to_current_end_does_not_follow() {
+        let mut overlay = TranscriptOverlay::new(
+            (0..20)
+                .map(|i| {
+                    Arc::new(TestCell {
+                        lines: vec![Line::from(format!("line{i}"))],
+                    }) as Arc<dyn HistoryCell>
+                })
+                .collect(),
+        );
+        let area = Rect::new(0, 0, 40, 12);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+
+        overlay.view.jump_to_current_end();
+        let pinned = overlay.view.scroll_offset;
+        assert_ne!(pinned, usize::MAX);
+        assert_eq!(pinned, overlay.view.max_scroll_offset());
+
+        overlay.insert_cells(vec![Arc::new(TestCell {
+            lines: vec!["tail".into()],
+        })]);
+        assert_eq!(overlay.view.scroll_offset, pinned);
+    }
+
+    #[test]
+    fn transcript_pause_survives_non_navigation_keys() {
+        let mut overlay = follow_overlay(TranscriptFollow::FollowWhenAtBottom);
+        let area = Rect::new(0, 0, 40, 12);
+        overlay.view.jump_to_current_end();
+        let pinned = overlay.view.scroll_offset;
+
+        for code in ['c', 'v', '#', 'W'] {
+            assert!(
+                overlay
+                    .view
+                    .apply_key_event(press(KeyCode::Char(code)), area)
+            );
+        }
+        assert!(overlay.view.hold_position);
+        push_tail(&mut overlay);
+        assert_eq!(overlay.view.scroll_offset, pinned);
+
+        // Scrolling releases the pause.
+        assert!(overlay.view.apply_key_event(press(KeyCode::Up), area));
+        assert!(!overlay.view.hold_position);
+    }
+
+    fn exec_cell_in(cwd: &str, cmd: &str) -> Arc<dyn HistoryCell> {
+        let mut cell = crate::exec_cell::new_active_exec_command(
+            format!("call-{cmd}"),
+            vec!["bash".into(), "-lc".into(), cmd.into()],
+            vec![ParsedCommand::Unknown { cmd: cmd.into() }],
+        );
+        cell.cwd = Some(PathBuf::from(cwd));
+        cell.complete_call(
+            &format!("call-{cmd}"),
+            CommandOutput {
+                exit_code: 0,
+                stdout: String::new(),
+                stderr: String::new(),
+                formatted_output: String::new(),
+                diagnostics: Vec::new(),
+            },
+            Duration::from_millis(10),
+        );
+        Arc::new(cell)
+    }
+
+    #[test]
+    fn appended_cells_render_like_a_full_rebuild() {
+        let cells: Vec<Arc<dyn HistoryCell>> = vec![
+            exec_cell_in("/work", "cargo build"),
+            Arc::new(TestCell {
+                lines: vec!["note".into()],
+            }),
+            exec_cell_in("/work", "cargo test"),
+            exec_cell_in("/other", "ls"),
+        ];
+        let mut overlay = TranscriptOverlay::new(Vec::new());
+        overlay.group_by_cwd = true;
+        overlay.stripe_style = Some(Style::default().bg(Color::DarkGray));
+        for cell in cells {
+            overlay.insert_cells(vec![cell]);
+        }
+        let appended = overlay.view.texts.clone();
+
+        overlay.rebuild_texts();
+        assert_eq!(overlay.view.texts, appended);
+        let headers = appended
+            .iter()
+            .flat_map(|text| text.lines.iter())
+            .filter(|line| line.spans.iter().any(|span| span.content.starts_with("— ")))
+            .count();
+        assert_eq!(headers, 2);
+    }
+
+    #[test]
+    fn transcript_search_hit_navigation_reports_line_and_column() {
+        let output = "src/main.rs:12:5:    let x = foo();\nsrc/lib.rs:3:17:pub fn foo() {";
+        let mut cell = crate::exec_cell::new_active_exec_command(
+            "call-rg".into(),
+            vec!["rg".into(), "--vimgrep".into(), "foo".into()],
+            vec![ParsedCommand::Unknown {
+                cmd: "rg --vimgrep foo".into(),
+            }],
+        );
+        cell.complete_call(
+            "call-rg",
+            CommandOutput {
+                exit_code: 0,
+                stdout: output.to_string(),
+                stderr: String::new(),
+                formatted_output: output.to_string(),
+                diagnostics: Vec::new(),
+            },
+            Duration::from_millis(10),
+        );
+        let mut overlay = TranscriptOverlay::new(vec![Arc::new(cell)]);
+
+        overlay.step_search_hit(true);
+        assert_eq!(
+            overlay.view.footer_note.as_deref(),
+            Some("hit 1 of 2: src/main.rs:12:5")
+        );
+        overlay.step_search_hit(true);
+        assert_eq!(
+            overlay.view.footer_note.as_deref(),
+            Some("hit 2 of 2: src/lib.rs:3:17")
+        );
+        overlay.step_search_hit(true);
+        assert_eq!(
+            overlay.view.footer_note.as_deref(),
+            Some("hit 1 of 2: src/main.rs:12:5")
+        );
+        overlay.step_search_hit(false);
+        assert_eq!(
+            overlay.view.footer_note.as_deref(),
+            Some("hit 2 of 2: src/lib.rs:3:17")
+        );
+    }
+
+    #[test]
+    fn transcript_diagnostic_navigation_visits_each_in_order() {
+        let mut filler: Vec<String> = (0..30).map(|i| format!("   Compiling dep{i}")).collect();
+        filler.extend([
+            "warning: unused variable: `y`".to_string(),
+            " --> src/lib.rs:3:9".to_string(),
+        ]);
+        filler.extend((0..30).map(|i| format!("note line {i}")));
+        filler.extend([
+            "error[E0425]: cannot find value `x` in this scope".to_string(),
+            "  --> src/main.rs:12:5".to_string(),
+        ]);
+        filler.extend((0..30).map(|i| format!("trailing {i}")));
+        let output = filler.join("\n");
+        let mut cell = crate::exec_cell::new_active_exec_command(
+            "call-build".into(),
+            vec!["bash".into(), "-lc".into(), "cargo build".into()],
+            vec![ParsedCommand::Unknown {
+                cmd: "cargo build".into(),
+            }],
+        );
+        cell.complete_call(
+            "call-build",
+            CommandOutput {
+                exit_code: 101,
+                stdout: String::new(),
+                stderr: output.clone(),
+                formatted_output: output,
+                diagnostics: Vec::new(),
+            },
+            Duration::from_secs(1),
+        );
+        let mut overlay = TranscriptOverlay::new(vec![Arc::new(cell)]);
+        let area = Rect::new(0, 0, 60, 14);
+        let mut buf = Buffer::empty(area);
+        overlay.view.scroll_offset = 0;
+        overlay.render(area, &mut buf);
+
+        let focused_row = |buf: &Buffer| {
+            (area.top()..area.bottom()).find(|&y| {
+                buf[(0, y)].modifier.contains(Modifier::REVERSED)
+                    && !buf[(0, y)].symbol().trim().is_empty()
+            })
+        };
+
+        overlay.step_diagnostic(true);
+        overlay.render(area, &mut buf);
+        let text = buffer_text(&buf);
+        assert!(
+            text.contains("diagnostic 1 of 2: warning at src/lib.rs:3:9"),
+            "{text}"
+        );
+        let row = focused_row(&buf).expect("focused line in view");
+        assert!(
+            text.lines()
+                .nth(row as usize)
+                .is_some_and(|l| l.starts_with("warning: unused variable")),
+            "{text}"
+        );
+
+        overlay.step_diagnostic(true);
+        overlay.render(area, &mut buf);
+        let text = buffer_text(&buf);
+        assert!(
+            text.contains("diagnostic 2 of 2: error at src/main.rs:12:5"),
+            "{text}"
+        );
+        let row = focused_row(&buf).expect("focused line in view");
+        assert!(
+            text.lines()
+                .nth(row as usize)
+                .is_some_and(|l| l.starts_with("error[E0425]")),
+            "{text}"
+        );
+
+        // Wraps back around to the first diagnostic.
+        overlay.step_diagnostic(true);
+        overlay.render(area, &mut buf);
+        assert!(buffer_text(&buf).contains("diagnostic 1 of 2: warning"));
+    }
+
+    #[test]
+    fn transcript_visible_cell_indices_match_viewport() {
+        let cells: Vec<Arc<dyn HistoryCell>> = (0..10)
+            .map(|i| {
+                Arc::new(TestCell {
+                    lines: vec![format!("cell {i} a").into(), format!("cell {i} b").into()],
+                }) as Arc<dyn HistoryCell>
+            })
+            .collect();
+        let mut overlay = TranscriptOverlay::new(cells);
+        // Each cell after the first takes a separator plus two lines.
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.view.scroll_offset = 7;
+        overlay.render(area, &mut buf);
+
+        // Five content rows from row 7: tail of cell 2, cell 3, head of cell 4.
+        assert_eq!(overlay.visible_cell_indices(), vec![2, 3, 4]);
+
+        // Indices stay relative to the full transcript once cells are capped.
+        overlay.max_cells = Some(5);
+        overlay.enforce_cell_limit();
+        overlay.view.scroll_offset = usize::MAX;
+        overlay.render(area, &mut buf);
+        assert_eq!(overlay.visible_cell_indices(), vec![8, 9]);
+    }
+
+    #[test]
+    fn transcript_open_cwd_passes_the_exec_cell_cwd_to_the_opener() {
+        let mut cell = crate::exec_cell::new_active_exec_command(
+            "call-ls".into(),
+            vec!["ls".into()],
+            vec![ParsedCommand::Unknown { cmd: "ls".into() }],
+        );
+        cell.cwd = Some(PathBuf::from("/work/project"));
+        let mut overlay = TranscriptOverlay::new(vec![Arc::new(cell)]);
+        overlay.render(
+            Rect::new(0, 0, 40, 10),
+            &mut Buffer::empty(Rect::new(0, 0, 40, 10)),
+        );
+
+        let mut opened = None;
+        overlay.open_cwd_with(|path| {
+            opened = Some(path.to_path_buf());
+            Ok(())
+        });
+        assert_eq!(opened, Some(PathBuf::from("/work/project")));
+        assert_eq!(
+            overlay
+                .view
+                .transient_footer
+                .as_ref()
+                .map(|(m, _)| m.as_str()),
+            Some("opened /work/project")
+        );
+
+        overlay.open_cwd_with(|_| Err(std::io::Error::other("no xdg-open")));
+        assert_eq!(
+            overlay
+                .view
+                .transient_footer
+                .as_ref()
+                .map(|(m, _)| m.as_str()),
+            Some("could not open file manager")
+        );
+    }
+
+    #[test]
+    fn transcript_edit_failed_command_prefills_the_quoted_command() {
+        let mut cell = crate::exec_cell::new_active_exec_command(
+            "call-test".into(),
+            vec!["cargo".into(), "test".into(), "--".into(), "my test".into()],
+            vec![ParsedCommand::Unknown {
+                cmd: "cargo test".into(),
+            }],
+        );
+        cell.complete_call(
+            "call-test",
+            CommandOutput {
+                exit_code: 101,
+                stdout: String::new(),
+                stderr: String::new(),
+                formatted_output: "test failed\n".into(),
+                diagnostics: Vec::new(),
+            },
+            Duration::from_millis(10),
+        );
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut overlay =
+            TranscriptOverlay::new(vec![exec_cell_in("/work", "cargo build"), Arc::new(cell)]);
+        overlay.app_event_tx = Some(AppEventSender::new(tx));
+
+        overlay.set_highlight_cell(Some(0));
+        overlay.edit_failed_command();
+        assert!(rx.try_recv().is_err());
+        assert_eq!(
+            overlay
+                .view
+                .transient_footer
+                .as_ref()
+                .map(|(m, _)| m.as_str()),
+            Some("no failed command here")
+        );
+
+        overlay.set_highlight_cell(Some(1));
+        overlay.edit_failed_command();
+        match rx.try_recv() {
+            Ok(AppEvent::PrefillComposer(text)) => {
+                assert_eq!(text, "cargo test -- 'my test'");
+            }
+            other => panic!("expected PrefillComposer, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn transcript_output_view_holds_every_output_line() {
+        let output: String = (0..20_000).map(|i| format!("row {i}\n")).collect();
+        let mut cell = crate::exec_cell::new_active_exec_command(
+            "call-big".into(),
+            vec!["bash".into(), "-lc".into(), "seq-ish".into()],
+            vec![ParsedCommand::Unknown {
+                cmd: "seq-ish".into(),
+            }],
+        );
+        cell.complete_call(
+            "call-big",
+            CommandOutput {
+                exit_code: 0,
+                stdout: output.clone(),
+                stderr: String::new(),
+                formatted_output: output,
+                diagnostics: Vec::new(),
+            },
+            Duration::from_secs(2),
+        );
+        let mut overlay = TranscriptOverlay::new(vec![Arc::new(cell)]);
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+
+        overlay.open_output_view();
+        let output_view = overlay.output_view.as_ref().expect("output view");
+        let lines = &output_view.view.texts[0].lines;
+        let plain = |line: &Line<'static>| -> String {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        };
+        assert_eq!(plain(&lines[0]), "$ seq-ish");
+        assert_eq!(plain(&lines[lines.len() - 1]), "[exit code: 0]");
+        let rows: Vec<String> = lines[1..lines.len() - 1].iter().map(plain).collect();
+        let expected: Vec<String> = (0..20_000).map(|i| format!("row {i}")).collect();
+        assert_eq!(rows, expected);
+    }
+
+    #[test]
+    fn transcript_export_markdown_fences_commands_and_patches() {
+        let mut changes = HashMap::new();
+        changes.insert(
+            PathBuf::from("/repo/src/new.rs"),
+            FileChange::Add {
+                content: "fn main() {}\n".to_string(),
+            },
+        );
+        let overlay = TranscriptOverlay::new(vec![
+            Arc::new(crate::history_cell::new_user_prompt("fix it".to_string())),
+            exec_cell_in("/repo", "make"),
+            Arc::new(new_patch_event(
+                PatchEventType::ApprovalRequest,
+                changes,
+                Path::new("/repo"),
+            )),
+            Arc::new(crate::history_cell::AgentMessageCell::new(
+                vec![Line::from("Done.")],
+                true,
+            )),
+            Arc::new(crate::history_cell::AgentMessageCell::new(
+                vec![Line::from("It builds now.")],
+                false,
+            )),
+        ]);
+
+        assert_eq!(
+            overlay.export_markdown(),
+            "**user**\n\n> fix it\n\n\
+             ```console\n$ make\n[exit code: 0]\n```\n\n\
+             ```diff\n--- /dev/null\n+++ b/src/new.rs\n+fn main() {}\n```\n\n\
+             **codex**\n\nDone.\nIt builds now.\n"
+        );
+    }
+
+    #[test]
+    fn transcript_answers_only_hides_everything_but_answers() {
+        let mut overlay = TranscriptOverlay::new(vec![
+            Arc::new(crate::history_cell::new_user_prompt("question".to_string())),
+            Arc::new(crate::history_cell::ReasoningSummaryCell::new(
+                Vec::new(),
+                vec![Line::from("thinking")],
+            )),
+            exec_cell_in("/repo", "make"),
+            Arc::new(crate::history_cell::AgentMessageCell::new(
+                vec![Line::from("first answer")],
+                true,
+            )),
+            Arc::new(crate::history_cell::AgentMessageCell::new(
+                vec![Line::from("answer continued")],
+                false,
+            )),
+            exec_cell_in("/repo", "make test"),
+            Arc::new(crate::history_cell::AgentMessageCell::new(
+                vec![Line::from("second answer")],
+                true,
+            )),
+        ]);
+        overlay.toggle_answers_only();
+
+        let rendered: Vec<String> = overlay
+            .view
+            .texts
+            .iter()
+            .flat_map(|text| text.lines.iter())
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "codex",
+                "first answer",
+                "answer continued",
+                "",
+                "codex",
+                "second answer",
+            ]
+        );
+        // Filtered cells keep their slot so indices still line up.
+        assert_eq!(overlay.view.texts.len(), 7);
+
+        overlay.toggle_answers_only();
+        let all = overlay
+            .view
+            .texts
+            .iter()
+            .map(|t| t.lines.len())
+            .sum::<usize>();
+        assert!(all > rendered.len());
+    }
+
+    #[test]
+    fn transcript_groups_exec_cells_by_cwd() {
+        let mut overlay = TranscriptOverlay::new(vec![
+            exec_cell_in("/repo", "make"),
+            exec_cell_in("/repo", "make test"),
+            exec_cell_in("/repo/sub", "cargo build"),
+        ]);
+        let area = Rect::new(0, 0, 60, 20);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        assert!(!buffer_to_text(&buf, area).contains("— /repo"));
+
+        overlay.toggle_group_by_cwd();
+        overlay.view.scroll_offset = 0;
+        overlay.render(area, &mut buf);
+        let text = buffer_to_text(&buf, area);
+        let rows: Vec<&str> = text.lines().collect();
+        let row_of = |needle: &str| rows.iter().position(|r| r.contains(needle));
+        let repo = row_of("— /repo —").expect("header for /repo");
+        let sub = row_of("— /repo/sub —").expect("header for /repo/sub");
+        let make_test = row_of("$ make test").expect("second command");
+        let cargo = row_of("$ cargo build").expect("third command");
+        assert!(repo < make_test && make_test < sub && sub < cargo, "{text}");
+        assert_eq!(text.matches("— /repo —").count(), 1, "{text}");
+    }
+
+    #[test]
+    fn static_overlay_snapshot_basic() {
+        // Prepare a static overlay with a few lines and a title
+        let mut overlay = StaticOverlay::with_title(
+            vec!["one".into(), "two".into(), "three".into()],
+            "S T A T I C".to_string(),
+        );
+        let mut term = Terminal::new(TestBackend::new(40, 10)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        assert_snapshot!(term.backend());
+    }
+
+    #[test]
+    fn bottom_bar_shows_all_when_content_fits_and_percent_otherwise() {
+        let area = Rect::new(0, 0, 40, 10);
+        let bottom_bar = |overlay: &mut StaticOverlay| -> String {
+            let mut buf = Buffer::empty(area);
+            overlay.render(area, &mut buf);
+            buffer_text(&buf)
+                .lines()
+                .nth(6)
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        let mut fits = StaticOverlay::with_title(vec!["one".into()], "S".to_string());
+        assert!(bottom_bar(&mut fits).ends_with(" All ─"));
+
+        let mut long = StaticOverlay::with_title(
+            (0..300).map(|i| Line::from(format!("line {i}"))).collect(),
+            "S".to_string(),
+        );
+        assert!(bottom_bar(&mut long).ends_with(" 0% ─"));
+        // One row short of the end is not 100%, even though it rounds to it.
+        long.view.scroll_offset = 295 - 1;
+        assert!(bottom_bar(&mut long).ends_with(" 99% ─"));
+        long.view.scroll_offset = usize::MAX;
+        assert!(bottom_bar(&mut long).ends_with(" 100% ─"));
+    }
+
+    #[test]
+    fn static_overlay_bottom_bar_shows_logical_line_position() {
+        let mut overlay = StaticOverlay::with_title(
+            (0..50).map(|i| Line::from(format!("line {i}"))).collect(),
+            "S T A T I C".to_string(),
+        );
+        overlay.view.show_line_position = true;
+        overlay.view.scroll_offset = 12;
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+
+        let text = buffer_text(&buf);
+        let bottom_bar = text.lines().nth(6).expect("bottom bar row");
+        assert!(bottom_bar.contains("line 13 of 50"), "got: {bottom_bar:?}");
+    }
+
+    #[test]
+    fn bottom_bar_toggles_between_source_lines_and_wrapped_rows() {
+        let mut lines: Vec<Line<'static>> =
+            (0..20).map(|i| Line::from(format!("line {i}"))).collect();
+        lines.insert(0, Line::from("x".repeat(100)));
+        let mut overlay = StaticOverlay::with_title(lines, "S".to_string());
+        let area = Rect::new(0, 0, 40, 10);
+        let c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE);
+        let bottom_bar = |overlay: &mut StaticOverlay| {
+            let mut buf = Buffer::empty(area);
+            overlay.render(area, &mut buf);
+            buffer_text(&buf)
+                .lines()
+                .nth(6)
+                .expect("bottom bar row")
+                .to_string()
+        };
+        bottom_bar(&mut overlay);
+        overlay.view.scroll_offset = 3;
+
+        // The 100-column first line wraps onto three rows.
+        assert!(overlay.view.apply_key_event(c, area));
+        let bar = bottom_bar(&mut overlay);
+        assert!(bar.contains("line 2 of 21"), "got: {bar:?}");
+
+        assert!(overlay.view.apply_key_event(c, area));
+        let bar = bottom_bar(&mut overlay);
+        assert!(bar.contains("row 4 of 23"), "got: {bar:?}");
+
+        // A third press goes back to the percentage.
+        assert!(overlay.view.apply_key_event(c, area));
+        let bar = bottom_bar(&mut overlay);
+        assert!(!bar.contains(" of "), "got: {bar:?}");
+        assert!(bar.contains('%'), "got: {bar:?}");
+    }
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn static_overlay_mark_jump_restores_scroll_offset() {
+        let mut overlay = StaticOverlay::with_title(
+            (0..50).map(|i| Line::from(format!("line {i}"))).collect(),
+            "S T A T I C".to_string(),
+        );
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        overlay.view.scroll_offset = 12;
+        overlay.render(area, &mut buf);
+
+        assert!(overlay.view.handle_mark_key(press(KeyCode::Char('m'))));
+        assert!(overlay.view.handle_mark_key(press(KeyCode::Char('a'))));
+
+        overlay.view.scroll_offset = 30;
+        overlay.render(area, &mut buf);
+
+        assert!(overlay.view.handle_mark_key(press(KeyCode::Char('\''))));
+        assert!(overlay.view.handle_mark_key(press(KeyCode::Char('a'))));
+        assert_eq!(overlay.view.scroll_offset, 12);
+
+        // Unknown marks leave the position untouched.
+        overlay.view.handle_mark_key(press(KeyCode::Char('\'')));
+        overlay.view.handle_mark_key(press(KeyCode::Char('b')));
+        assert_eq!(overlay.view.scroll_offset, 12);
+    }
+
+    #[test]
+    fn pager_wheel_scrolls_three_lines_and_clamps() {
+        let mut pv = PagerView::new(
+            vec![Text::from(
+                (0..20)
+                    .map(|i| Line::from(format!("line {i}")))
+                    .collect::<Vec<_>>(),
+            )],
+            "T".to_string(),
+            0,
+        );
+        let area = Rect::new(0, 0, 40, 7);
+        let mut buf = Buffer::empty(area);
+        pv.render(area, &mut buf);
+
+        assert!(pv.scroll_by_wheel(MouseEventKind::ScrollDown));
+        assert_eq!(pv.scroll_offset, 3);
+        assert!(pv.scroll_by_wheel(MouseEventKind::ScrollUp));
+        assert_eq!(pv.scroll_offset, 0);
+        assert!(!pv.scroll_by_wheel(MouseEventKind::Moved));
+
+        // Scrolling past the end clamps and reports 100%.
+        for _ in 0..10 {
+            pv.scroll_by_wheel(MouseEventKind::ScrollDown);
+        }
+        pv.render(area, &mut buf);
+        assert_eq!(pv.scroll_offset, 15);
+        assert!(buffer_text(&buf).contains("100%"));
+
+        // From the follow-bottom sentinel, one notch up moves off the end.
+        pv.scroll_offset = usize::MAX;
+        pv.scroll_by_wheel(MouseEventKind::ScrollUp);
+        assert_eq!(pv.scroll_offset, 12);
+    }
+
+    #[test]
+    fn pager_visible_page_text_matches_rendered_rows() {
+        let mut pv = PagerView::new(
+            vec![Text::from(
+                (0..20)
+                    .map(|i| Line::from(format!("line {i}")))
+                    .collect::<Vec<_>>(),
+            )],
+            "T".to_string(),
+            4,
+        );
+        let area = Rect::new(0, 0, 40, 5);
+        let mut buf = Buffer::empty(area);
+        pv.render(area, &mut buf);
+        assert_eq!(pv.visible_page_text(), "line 4\nline 5\nline 6");
+    }
+
+    #[test]
+    fn snapshot_key_spec_parses_function_keys_and_modifiers() {
+        assert_eq!(
+            parse_key_spec("f12"),
+            Some((KeyCode::F(12), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            parse_key_spec("Ctrl+s"),
+            Some((KeyCode::Char('s'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            parse_key_spec("ctrl+alt+d"),
+            Some((
+                KeyCode::Char('d'),
+                KeyModifiers::CONTROL | KeyModifiers::ALT
+            ))
+        );
+        assert_eq!(
+            parse_key_spec("f"),
+            Some((KeyCode::Char('f'), KeyModifiers::NONE))
+        );
+        assert_eq!(parse_key_spec("f13"), None);
+        assert_eq!(parse_key_spec("shift+x"), None);
+        assert_eq!(parse_key_spec("enter"), None);
+
+        let overlay = Overlay::new_static_with_title(vec![Line::from("body")], "S".to_string())
+            .with_snapshot_key(Some("ctrl+s"));
+        assert_eq!(
+            overlay.view().snapshot_key,
+            (KeyCode::Char('s'), KeyModifiers::CONTROL)
+        );
+    }
+
+    #[test]
+    fn debug_snapshot_records_transcript_content_and_scroll() {
+        let cells: Vec<Arc<dyn HistoryCell>> = (0..20)
+            .map(|i| -> Arc<dyn HistoryCell> {
+                Arc::new(TestCell {
+                    lines: vec![Line::from(format!("cell{i}"))],
+                })
+            })
+            .collect();
+        let mut overlay = Overlay::new_transcript(cells);
+        let area = Rect::new(0, 0, 30, 12);
+        let mut buf = Buffer::empty(area);
+        overlay.view_mut().scroll_offset = 4;
+        overlay.render_into(area, &mut buf);
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = overlay
+            .write_debug_snapshot(dir.path())
+            .expect("write snapshot");
+        let snapshot = std::fs::read_to_string(path).expect("read snapshot");
+        assert!(
+            snapshot.starts_with("overlay: transcript\ncells: 20\n"),
+            "{snapshot}"
+        );
+        assert!(snapshot.contains("scroll_offset: 4\n"), "{snapshot}");
+        assert!(snapshot.contains("content_height: 7\n"), "{snapshot}");
+        assert!(snapshot.contains("wrap_width: 30\n"), "{snapshot}");
+        assert!(snapshot.contains("\ncell0\n"), "{snapshot}");
+        assert!(snapshot.contains("\ncell19\n"), "{snapshot}");
+    }
+
+    #[test]
+    fn debug_snapshot_dumps_the_open_output_view() {
+        let mut cell = crate::exec_cell::new_active_exec_command(
+            "call-ls".into(),
+            vec!["bash".into(), "-lc".into(), "ls".into()],
+            vec![ParsedCommand::Unknown { cmd: "ls".into() }],
+        );
+        cell.complete_call(
+            "call-ls",
+            CommandOutput {
+                exit_code: 0,
+                stdout: "a.txt\nb.txt\n".into(),
+                stderr: String::new(),
+                formatted_output: "a.txt\nb.txt\n".into(),
+                diagnostics: Vec::new(),
+            },
+            Duration::from_millis(5),
+        );
+        let mut transcript = TranscriptOverlay::new(vec![
+            Arc::new(TestCell {
+                lines: vec![Line::from("earlier cell")],
+            }),
+            Arc::new(cell),
+        ]);
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        transcript.render(area, &mut buf);
+        transcript.open_output_view();
+        let mut overlay = Overlay::Transcript(transcript);
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = overlay
+            .write_debug_snapshot(dir.path())
+            .expect("write snapshot");
+        let snapshot = std::fs::read_to_string(path).expect("read snapshot");
+        assert!(
+            snapshot.starts_with("overlay: transcript output\ntitle: O U T P U T\n"),
+            "{snapshot}"
+        );
+        assert!(snapshot.contains("\na.txt\nb.txt\n"), "{snapshot}");
+        assert!(!snapshot.contains("earlier cell"), "{snapshot}");
+
+        // The confirmation lands on the view being drawn.
+        overlay
+            .active_view_mut()
+            .set_transient_footer("snapshot saved".to_string(), SNAPSHOT_FOOTER_DURATION);
+        let Overlay::Transcript(transcript) = &overlay else {
+            unreachable!();
+        };
+        let output_view = transcript.output_view.as_ref().expect("output view");
+        assert!(output_view.view.transient_footer.is_some());
+        assert!(transcript.view.transient_footer.is_none());
+    }
+
+    #[test]
+    fn transient_footer_replaces_hints_until_it_expires() {
+        let mut overlay = Overlay::new_static_with_title(vec![Line::from("body")], "S".to_string());
+        let area = Rect::new(0, 0, 40, 8);
+        let mut buf = Buffer::empty(area);
+        overlay.set_transient_footer(
+            "saved to /tmp/out.md".to_string(),
+            Duration::from_secs(5),
+            &FrameRequester::test_dummy(),
+        );
+        overlay.render_into(area, &mut buf);
+        let text = buffer_text(&buf);
+        assert!(text.contains("saved to /tmp/out.md"), "{text}");
+        assert!(!text.contains("quit"), "{text}");
+
+        // Expire it, as if the duration had elapsed.
+        let Overlay::Static(static_overlay) = &mut overlay else {
+            unreachable!("static overlay");
+        };
+        if let Some((_, until)) = static_overlay.view.transient_footer.as_mut() {
+            *until = Instant::now();
+        }
+        let mut buf = Buffer::empty(area);
+        overlay.render_into(area, &mut buf);
+        let text = buffer_text(&buf);
+        assert!(!text.contains("saved to"), "{text}");
+        assert!(text.contains("quit"), "{text}");
+    }
+
     #[test]
-    fn edit_prev_hint_is_visible() {
-        let mut overlay = TranscriptOverlay::new(vec![Arc::new(TestCell {
-            lines: vec![Line::from("hello")],
-        })]);
-
-        // Render into a small buffer and assert the backtrack hint is present
-        let area = Rect::new(0, 0, 40, 10);
+    fn static_overlay_line_number_gutter_marks_logical_lines() {
+        let mut lines: Vec<Line<'static>> = (1..=11).map(|i| Line::from(format!("l{i}"))).collect();
+        lines.insert(1, Line::from("aaaa bbbb cccc dddd eeee"));
+        let mut overlay = StaticOverlay::with_title(lines, "S".to_string());
+        overlay.view.show_line_numbers = true;
+        let area = Rect::new(0, 0, 15, 10);
         let mut buf = Buffer::empty(area);
         overlay.render(area, &mut buf);
 
-        // Flatten buffer to a string and check for the hint text
-        let mut s = String::new();
-        for y in area.y..area.bottom() {
-            for x in area.x..area.right() {
-                s.push(buf[(x, y)].symbol().chars().next().unwrap_or(' '));
-            }
-            s.push('\n');
-        }
-        assert!(
-            s.contains("edit prev"),
-            "expected 'edit prev' hint in overlay footer, got: {s:?}"
+        // 12 lines need two digits plus a space, leaving 12 columns of text.
+        assert_eq!(overlay.view.gutter_width(), 3);
+        assert_eq!(overlay.view.wrap_cache.as_ref().map(|c| c.width), Some(12));
+        let text = buffer_text(&buf);
+        let rows: Vec<&str> = text.lines().skip(1).take(5).map(str::trim_end).collect();
+        assert_eq!(
+            rows,
+            vec![" 1 l1", " 2 aaaa bbbb", "   cccc dddd", "   eeee", " 3 l2",],
+            "{text}"
         );
     }
 
     #[test]
-    fn transcript_overlay_snapshot_basic() {
-        // Prepare a transcript overlay with a few lines
-        let mut overlay = TranscriptOverlay::new(vec![
-            Arc::new(TestCell {
-                lines: vec![Line::from("alpha")],
-            }),
-            Arc::new(TestCell {
-                lines: vec![Line::from("beta")],
-            }),
-            Arc::new(TestCell {
-                lines: vec![Line::from("gamma")],
-            }),
-        ]);
-        let mut term = Terminal::new(TestBackend::new(40, 10)).expect("term");
-        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
-            .expect("draw");
-        assert_snapshot!(term.backend());
+    fn page_step_keeps_configured_overlap() {
+        let mut pv = PagerView::new(vec![Text::from("x")], "T".to_string(), 0);
+        let content = Rect::new(0, 0, 40, 10);
+        assert_eq!(pv.page_step(content), 10);
+
+        pv.page_overlap = 2;
+        assert_eq!(pv.page_step(content), 8);
+
+        // A huge overlap still makes progress.
+        pv.page_overlap = 50;
+        assert_eq!(pv.page_step(content), 1);
     }
 
-    fn buffer_to_text(buf: &Buffer, area: Rect) -> String {
-        let mut out = String::new();
-        for y in area.y..area.bottom() {
-            for x in area.x..area.right() {
-                let symbol = buf[(x, y)].symbol();
-                if symbol.is_empty() {
-                    out.push(' ');
-                } else {
-                    out.push(symbol.chars().next().unwrap_or(' '));
-                }
-            }
-            // Trim trailing spaces for stability.
-            while out.ends_with(' ') {
-                out.pop();
-            }
-            out.push('\n');
-        }
-        out
+    #[test]
+    fn pager_ctrl_d_and_ctrl_u_scroll_half_a_page() {
+        let lines: Vec<Line<'static>> = (0..50).map(|i| Line::from(format!("line {i}"))).collect();
+        let mut pv = PagerView::new(vec![Text::from(lines)], "T".to_string(), 0);
+        let area = Rect::new(0, 0, 20, 10);
+        pv.render(area, &mut Buffer::empty(area));
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+
+        // The content area is 8 rows tall, so each step moves 4.
+        assert!(pv.apply_key_event(ctrl('d'), area));
+        assert!(pv.apply_key_event(ctrl('d'), area));
+        assert_eq!(pv.scroll_offset(), 8);
+        assert!(pv.apply_key_event(ctrl('u'), area));
+        assert_eq!(pv.scroll_offset(), 4);
+        assert!(pv.apply_key_event(ctrl('u'), area));
+        assert!(pv.apply_key_event(ctrl('u'), area));
+        assert_eq!(pv.scroll_offset(), 0);
     }
 
     #[test]
-    fn transcript_overlay_apply_patch_scroll_vt100_clears_previous_page() {
-        let cwd = PathBuf::from("/repo");
-        let mut cells: Vec<Arc<dyn HistoryCell>> = Vec::new();
+    fn pager_needs_redraw_after_scroll_until_rendered() {
+        let lines: Vec<Line<'static>> = (0..20).map(|i| Line::from(format!("line {i}"))).collect();
+        let mut pv = PagerView::new(vec![Text::from(lines)], "T".to_string(), 0);
+        let area = Rect::new(0, 0, 20, 8);
+        let mut buf = Buffer::empty(area);
+        assert!(pv.needs_redraw());
 
-        let mut approval_changes = HashMap::new();
-        approval_changes.insert(
-            PathBuf::from("foo.txt"),
-            FileChange::Add {
-                content: "hello\nworld\n".to_string(),
-            },
-        );
-        let approval_cell: Arc<dyn HistoryCell> = Arc::new(new_patch_event(
-            PatchEventType::ApprovalRequest,
-            approval_changes,
-            &cwd,
-        ));
-        cells.push(approval_cell);
+        pv.render(area, &mut buf);
+        assert!(!pv.needs_redraw());
 
-        let mut apply_changes = HashMap::new();
-        apply_changes.insert(
-            PathBuf::from("foo.txt"),
-            FileChange::Add {
-                content: "hello\nworld\n".to_string(),
-            },
-        );
-        let apply_begin_cell: Arc<dyn HistoryCell> = Arc::new(new_patch_event(
-            PatchEventType::ApplyBegin {
-                auto_approved: false,
-            },
-            apply_changes,
-            &cwd,
-        ));
-        cells.push(apply_begin_cell);
+        pv.scroll_offset += 1;
+        assert!(pv.needs_redraw());
+        pv.render(area, &mut buf);
+        assert!(!pv.needs_redraw());
 
-        let apply_end_cell: Arc<dyn HistoryCell> =
-            Arc::new(crate::history_cell::new_user_approval_decision(vec![
-                "✓ Patch applied".green().bold().into(),
-                "src/foo.txt".dim().into(),
-            ]));
-        cells.push(apply_end_cell);
+        pv.texts.push(Text::from("appended"));
+        assert!(pv.needs_redraw());
+        pv.render(area, &mut buf);
+        assert!(!pv.needs_redraw());
+    }
 
-        let mut exec_cell = crate::exec_cell::new_active_exec_command(
-            "exec-1".into(),
-            vec!["bash".into(), "-lc".into(), "ls".into()],
-            vec![ParsedCommand::Unknown { cmd: "ls".into() }],
-        );
-        exec_cell.complete_call(
-            "exec-1",
-            CommandOutput {
-                exit_code: 0,
-                stdout: "src\nREADME.md\n".into(),
-                stderr: String::new(),
-                formatted_output: "src\nREADME.md\n".into(),
-            },
-            Duration::from_millis(420),
+    #[test]
+    fn pager_search_accumulates_matches_across_renders() {
+        let lines: Vec<Line<'static>> = (0..5 * SEARCH_LINES_PER_FRAME)
+            .map(|i| {
+                if i % 100 == 0 {
+                    Line::from(format!("Needle at {i}"))
+                } else {
+                    Line::from(format!("hay {i}"))
+                }
+            })
+            .collect();
+        let mut pv = PagerView::new(vec![Text::from(lines)], "T".to_string(), 0);
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        pv.start_search("needle");
+
+        let per_frame = SEARCH_LINES_PER_FRAME / 100;
+        for frame in 1..=5 {
+            assert!(pv.search_in_progress());
+            pv.render(area, &mut buf);
+            let search = pv.search.as_ref().expect("search");
+            assert_eq!(search.matches.len(), frame * per_frame);
+        }
+        assert!(!pv.search_in_progress());
+        assert_eq!(
+            pv.search.as_ref().and_then(|s| s.matches.get(1)).copied(),
+            Some(SearchMatch {
+                line: 100,
+                start: 0
+            })
         );
-        let exec_cell: Arc<dyn HistoryCell> = Arc::new(exec_cell);
-        cells.push(exec_cell);
+    }
 
-        let mut overlay = TranscriptOverlay::new(cells);
-        let area = Rect::new(0, 0, 80, 12);
+    #[test]
+    fn pager_search_prompt_jumps_between_matches() {
+        let lines: Vec<Line<'static>> = (0..60)
+            .map(|i| {
+                if i % 20 == 5 {
+                    Line::from(format!("Error at {i}"))
+                } else {
+                    Line::from(format!("ok {i}"))
+                }
+            })
+            .collect();
+        let mut pv = PagerView::new(vec![Text::from(lines)], "T".to_string(), 0);
+        let area = Rect::new(0, 0, 40, 7);
         let mut buf = Buffer::empty(area);
+        pv.render(area, &mut buf);
 
-        overlay.render(area, &mut buf);
-        overlay.view.scroll_offset = 0;
-        overlay.view.wrap_cache = None;
-        overlay.render(area, &mut buf);
+        // What `/` does.
+        pv.search_prompt = Some(String::new());
+        for key in [
+            KeyCode::Char('e'),
+            KeyCode::Char('R'),
+            KeyCode::Char('x'),
+            KeyCode::Backspace,
+            KeyCode::Char('r'),
+        ] {
+            assert!(pv.handle_search_prompt_key(press(key)));
+        }
+        pv.render(area, &mut buf);
+        assert!(buffer_text(&buf).contains(" /eRr "));
 
-        let snapshot = buffer_to_text(&buf, area);
-        assert_snapshot!("transcript_overlay_apply_patch_scroll_vt100", snapshot);
+        assert!(pv.handle_search_prompt_key(press(KeyCode::Enter)));
+        assert!(pv.search_prompt.is_none());
+        pv.render(area, &mut buf);
+        let text = buffer_text(&buf);
+        assert!(text.contains(" 1/3 "), "{text}");
+        assert!(text.contains("Error at 5"), "{text}");
+        // Matches are drawn reversed.
+        let row = text
+            .lines()
+            .position(|l| l.starts_with("Error at 5"))
+            .expect("match row") as u16;
+        assert!(buf[(0, row)].modifier.contains(Modifier::REVERSED));
+        assert!(!buf[(6, row)].modifier.contains(Modifier::REVERSED));
+
+        pv.step_search(true);
+        pv.render(area, &mut buf);
+        let text = buffer_text(&buf);
+        assert!(text.contains(" 2/3 "), "{text}");
+        assert!(text.contains("Error at 25"), "{text}");
+
+        pv.step_search(false);
+        pv.step_search(false);
+        pv.render(area, &mut buf);
+        let text = buffer_text(&buf);
+        assert!(text.contains(" 3/3 "), "{text}");
+        assert!(text.contains("Error at 45"), "{text}");
     }
 
     #[test]
-    fn transcript_overlay_keeps_scroll_pinned_at_bottom() {
-        let mut overlay = TranscriptOverlay::new(
-            (0..20)
-                .map(|i| {
-                    Arc::new(TestCell {
-                        lines: vec![Line::from(format!("line{i}"))],
-                    }) as Arc<dyn HistoryCell>
-                })
-                .collect(),
-        );
-        let mut term = Terminal::new(TestBackend::new(40, 12)).expect("term");
-        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
-            .expect("draw");
+    fn pager_search_match_across_wrap_shows_its_first_row() {
+        let long = format!("{} needle here", "word ".repeat(20));
+        let mut lines: Vec<Line<'static>> = (0..30).map(|i| Line::from(format!("x{i}"))).collect();
+        lines.push(Line::from(long));
+        let mut pv = PagerView::new(vec![Text::from(lines)], "T".to_string(), 0);
+        let area = Rect::new(0, 0, 20, 6);
+        let mut buf = Buffer::empty(area);
+        pv.start_search("needle");
+        pv.pending_search_jump = true;
+        pv.render(area, &mut buf);
 
-        assert!(
-            overlay.view.is_scrolled_to_bottom(),
-            "expected initial render to leave view at bottom"
-        );
+        let cache = pv.wrap_cache.as_ref().expect("wrap cache");
+        let m = pv.search.as_ref().expect("search").matches[0];
+        let row = cache.match_row(m).expect("row");
+        assert!(cache.line_range(30).expect("range").start < row);
+        let rendered: String = cache.wrapped[row]
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert!(rendered.contains("needle"), "{rendered:?}");
+        assert!(pv.scroll_offset <= row && row < pv.scroll_offset + 4);
+    }
 
-        overlay.insert_cell(Arc::new(TestCell {
-            lines: vec!["tail".into()],
-        }));
+    #[test]
+    fn pager_search_footer_marks_partial_counts() {
+        let lines: Vec<Line<'static>> = (0..2 * SEARCH_LINES_PER_FRAME)
+            .map(|i| Line::from(format!("needle {i}")))
+            .collect();
+        let mut pv = PagerView::new(vec![Text::from(lines)], "T".to_string(), 0);
+        let area = Rect::new(0, 0, 40, 10);
+        let mut buf = Buffer::empty(area);
+        pv.start_search("NEEDLE");
 
-        assert_eq!(overlay.view.scroll_offset, usize::MAX);
+        pv.render(area, &mut buf);
+        let footer = buffer_text(&buf)
+            .lines()
+            .nth(9)
+            .unwrap_or_default()
+            .to_string();
+        assert!(footer.contains(" 2000+ matches "), "got: {footer:?}");
+
+        pv.render(area, &mut buf);
+        let footer = buffer_text(&buf)
+            .lines()
+            .nth(9)
+            .unwrap_or_default()
+            .to_string();
+        assert!(footer.contains(" 4000 matches "), "got: {footer:?}");
     }
 
     #[test]
-    fn transcript_overlay_preserves_manual_scroll_position() {
-        let mut overlay = TranscriptOverlay::new(
-            (0..20)
-                .map(|i| {
-                    Arc::new(TestCell {
-                        lines: vec![Line::from(format!("line{i}"))],
-                    }) as Arc<dyn HistoryCell>
-                })
-                .collect(),
-        );
-        let mut term = Terminal::new(TestBackend::new(40, 12)).expect("term");
-        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
-            .expect("draw");
+    fn last_wrap_width_tracks_the_content_area() {
+        let mut overlay = Overlay::new_static_with_title(vec![Line::from("body")], "S".to_string());
+        assert_eq!(overlay.view().last_wrap_width(), None);
 
-        overlay.view.scroll_offset = 0;
+        let area = Rect::new(0, 0, 40, 8);
+        let mut buf = Buffer::empty(area);
+        overlay.render_into(area, &mut buf);
+        assert_eq!(overlay.view().last_wrap_width(), Some(40));
 
-        overlay.insert_cell(Arc::new(TestCell {
-            lines: vec!["tail".into()],
-        }));
+        // The line number gutter narrows the content area.
+        overlay.view_mut().show_line_numbers = true;
+        overlay.render_into(area, &mut buf);
+        let gutter = overlay.view().gutter_width();
+        assert!(gutter > 0);
+        assert_eq!(overlay.view().last_wrap_width(), Some(40 - gutter));
 
-        assert_eq!(overlay.view.scroll_offset, 0);
+        overlay.view_mut().max_wrap_width = 10;
+        overlay.render_into(area, &mut buf);
+        assert_eq!(overlay.view().last_wrap_width(), Some(10));
     }
 
     #[test]
-    fn static_overlay_snapshot_basic() {
-        // Prepare a static overlay with a few lines and a title
-        let mut overlay = StaticOverlay::with_title(
-            vec!["one".into(), "two".into(), "three".into()],
-            "S T A T I C".to_string(),
-        );
-        let mut term = Terminal::new(TestBackend::new(40, 10)).expect("term");
-        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
-            .expect("draw");
-        assert_snapshot!(term.backend());
+    fn pager_wrap_width_is_clamped() {
+        let long = "word ".repeat(400);
+        let mut pv = PagerView::new(vec![Text::from(vec![long.into()])], "T".to_string(), 0);
+
+        pv.ensure_wrapped(u16::MAX);
+        let cache = pv.wrap_cache.as_ref().expect("wrap cache");
+        assert_eq!(cache.width, DEFAULT_MAX_WRAP_WIDTH);
+        assert!(cache.wrapped.len() > 1);
+
+        pv.max_wrap_width = 80;
+        pv.ensure_wrapped(u16::MAX);
+        let cache = pv.wrap_cache.as_ref().expect("wrap cache");
+        assert_eq!(cache.width, 80);
+        assert!(cache.max_width <= 80);
     }
 
     #[test]
@@ -815,6 +5311,212 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pager_long_lines_only_mode_wraps_just_the_long_lines() {
+        let short = "short line";
+        let medium = "m".repeat(30);
+        let long = "word ".repeat(20);
+        let mut pv = PagerView::new(
+            vec![Text::from(vec![
+                short.into(),
+                medium.clone().into(),
+                long.into(),
+            ])],
+            "T".to_string(),
+            0,
+        );
+        pv.wrap_mode = WrapMode::LongLinesOnly;
+        pv.ensure_wrapped(20);
+
+        let cache = pv.wrap_cache.as_ref().expect("wrap cache");
+        // The short and medium (under 2x width) lines stay on one row each;
+        // only the 100-column line wraps.
+        assert_eq!(cache.line_starts, vec![0, 1, 2]);
+        assert_eq!(cache.wrapped[1].width(), medium.len());
+        assert!(cache.wrapped.len() > 3);
+
+        // Switching back to wrapping everything rebuilds the cache.
+        pv.wrap_mode = WrapMode::All;
+        pv.ensure_wrapped(20);
+        let cache = pv.wrap_cache.as_ref().expect("wrap cache");
+        assert_eq!(cache.line_starts, vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn pager_long_lines_only_mode_scrolls_to_the_clipped_tail() {
+        // 1.5 viewports wide: kept on one row and clipped at the edge.
+        let wide = format!("{}END", "abcdefgh".repeat(3));
+        let mut pv = PagerView::new(
+            vec![Text::from(vec![wide.clone().into()])],
+            "T".to_string(),
+            0,
+        );
+        let area = Rect::new(0, 0, 20, 5);
+        let mut buf = Buffer::empty(area);
+        pv.wrap_mode = WrapMode::LongLinesOnly;
+        pv.render(area, &mut buf);
+        let text = buffer_text(&buf);
+        assert!(!text.contains("END"), "{text}");
+
+        for _ in 0..2 {
+            assert!(pv.apply_key_event(KeyEvent::from(KeyCode::Right), area));
+        }
+        pv.render(area, &mut buf);
+        assert_eq!(pv.h_offset, wide.len() - 20);
+        let text = buffer_text(&buf);
+        assert!(text.lines().nth(1).expect("row").ends_with("END"), "{text}");
+        assert!(text.contains("col 8"), "{text}");
+    }
+
+    #[test]
+    fn pager_no_wrap_mode_clips_and_scrolls_horizontally() {
+        let wide = format!("{}END", "abcdefgh".repeat(4));
+        let mut pv = PagerView::new(
+            vec![Text::from(vec![wide.clone().into(), "short".into()])],
+            "T".to_string(),
+            0,
+        );
+        let area = Rect::new(0, 0, 20, 5);
+        let mut buf = Buffer::empty(area);
+        pv.wrap_mode = WrapMode::None;
+        pv.render(area, &mut buf);
+
+        let cache = pv.wrap_cache.as_ref().expect("wrap cache");
+        assert_eq!(cache.wrapped.len(), 2);
+        assert_eq!(cache.max_width, wide.len());
+        let text = buffer_text(&buf);
+        let rows: Vec<&str> = text.lines().collect();
+        assert_eq!(rows[1], "abcdefghabcdefghabcd");
+        assert!(rows[4].contains("col 1"), "{rows:?}");
+
+        pv.h_offset = 8;
+        pv.render(area, &mut buf);
+        let text = buffer_text(&buf);
+        let rows: Vec<&str> = text.lines().collect();
+        assert_eq!(rows[1], "abcdefghabcdefghabcd");
+        assert!(rows[4].contains("col 9"), "{rows:?}");
+
+        // Scrolling past the widest line stops with its end at the right edge.
+        pv.h_offset = 100;
+        pv.render(area, &mut buf);
+        assert_eq!(pv.h_offset, wide.len() - 20);
+        let text = buffer_text(&buf);
+        assert!(text.lines().nth(1).expect("row").ends_with("END"));
+
+        // Switching back to wrapping rebuilds the cache and resets the offset.
+        pv.wrap_mode = WrapMode::All;
+        pv.render(area, &mut buf);
+        assert_eq!(pv.h_offset, 0);
+        assert!(pv.wrap_cache.as_ref().expect("wrap cache").wrapped.len() > 2);
+    }
+
+    #[test]
+    fn skip_columns_keeps_styles_and_drops_straddling_wide_chars() {
+        let line = Line::from(vec!["ab".red(), "界c".into()]);
+        let skipped = skip_columns(line.clone(), 1);
+        assert_eq!(skipped.spans[0].content, "b");
+        assert_eq!(skipped.spans[0].style.fg, Some(Color::Red));
+        assert_eq!(skip_columns(line.clone(), 3).spans[0].content, "c");
+        assert_eq!(skip_columns(line, 4).spans[0].content, "c");
+    }
+
+    #[test]
+    fn pager_on_scroll_reports_key_and_programmatic_scrolls() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let lines: Vec<Line<'static>> = (0..50).map(|i| Line::from(format!("line {i}"))).collect();
+        let mut pv = PagerView::new(vec![Text::from(lines)], "T".to_string(), 0);
+        let area = Rect::new(0, 0, 20, 10);
+        pv.render(area, &mut Buffer::empty(area));
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&seen);
+        pv.set_on_scroll(move |offset| sink.borrow_mut().push(offset));
+
+        assert!(pv.apply_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE), area));
+        assert_eq!(*seen.borrow(), vec![1]);
+        assert_eq!(pv.scroll_offset(), 1);
+
+        // Setting the offset it already has (as a mirroring pager would) is
+        // not reported again.
+        pv.set_scroll_offset(1);
+        pv.set_scroll_offset(5);
+        assert_eq!(*seen.borrow(), vec![1, 5]);
+
+        // The follow-bottom sentinel is reported as the concrete bottom row,
+        // and rendering there reports nothing new.
+        pv.set_scroll_offset(usize::MAX);
+        pv.render(area, &mut Buffer::empty(area));
+        assert_eq!(*seen.borrow(), vec![1, 5, 42]);
+    }
+
+    #[test]
+    fn pager_visible_page_text_follows_copy_keeps_wraps() {
+        let long = "alpha beta gamma delta epsilon zeta";
+        let mut pv = PagerView::new(vec![Text::from(long)], "T".to_string(), 0);
+        pv.ensure_wrapped(12);
+        pv.scroll_offset = 0;
+        pv.last_content_height = Some(2);
+
+        // Only two of the three rows are on screen; the line is copied whole.
+        assert_eq!(pv.visible_page_text(), long);
+
+        pv.toggle_copy_keeps_wraps();
+        assert_eq!(
+            pv.visible_page_text(),
+            "alpha beta\ngamma delta\nepsilon zeta"
+        );
+    }
+
+    #[test]
+    fn pager_copy_text_unwraps_or_keeps_soft_wraps() {
+        let long = "alpha beta gamma delta epsilon zeta";
+        let mut pv = PagerView::new(vec![Text::from(long)], "T".to_string(), 0);
+        pv.ensure_wrapped(12);
+        let text = format!("$ echo\n{long}\n");
+
+        assert_eq!(pv.text_for_copy(&text), text);
+
+        pv.toggle_copy_keeps_wraps();
+        assert_eq!(
+            pv.text_for_copy(&text),
+            "$ echo\nalpha beta\ngamma delta\nepsilon zeta\n"
+        );
+    }
+
+    #[test]
+    fn pager_control_pictures_show_control_bytes_when_enabled() {
+        let mut pv = PagerView::new(vec![Text::from("bell\u{7}nul\u{0}end")], "T".to_string(), 0);
+        let area = Rect::new(0, 0, 30, 4);
+        let mut buf = Buffer::empty(area);
+        pv.render(area, &mut buf);
+        assert!(!buffer_text(&buf).contains('\u{2407}'));
+
+        pv.show_control_pictures = true;
+        pv.render(area, &mut buf);
+        let text = buffer_text(&buf);
+        let row = text.lines().nth(1).expect("content row");
+        assert!(row.starts_with("bell\u{2407}nul\u{2400}end"), "{row:?}");
+        assert!(buf[(4, 1)].modifier.contains(Modifier::DIM));
+        assert!(!buf[(0, 1)].modifier.contains(Modifier::DIM));
+    }
+
+    #[test]
+    fn pager_force_rewrap_rebuilds_cache_on_next_wrap() {
+        let mut pv = PagerView::new(vec![Text::from("some content")], "T".to_string(), 0);
+        pv.ensure_wrapped(20);
+        assert!(pv.wrap_cache.is_some());
+
+        pv.force_rewrap();
+        assert!(pv.wrap_cache.is_none());
+
+        pv.ensure_wrapped(20);
+        let cache = pv.wrap_cache.as_ref().expect("wrap cache");
+        assert_eq!(cache.width, 20);
+        assert_eq!(cache.wrapped.len(), 1);
+    }
+
     #[test]
     fn pager_wrap_cache_invalidates_on_append() {
         let long = "Another long line for wrapping behavior verification.";
@@ -1,13 +1,9 @@
-use crate::exec_command::relativize_to_home;
-use crate::text_formatting;
 use chrono::DateTime;
 use chrono::Local;
 use codex_core::auth::get_auth_file;
 use codex_core::auth::try_read_auth_json;
 use codex_core::config::Config;
 use codex_core::project_doc::discover_project_doc_paths;
-use std::path::Path;
-use unicode_width::UnicodeWidthStr;
 
 use super::account::StatusAccountDisplay;
 
@@ -134,29 +130,6 @@ pub(crate) fn format_tokens_compact(value: u64) -> String {
     format!("{formatted}{suffix}")
 }
 
-pub(crate) fn format_directory_display(directory: &Path, max_width: Option<usize>) -> String {
-    let formatted = if let Some(rel) = relativize_to_home(directory) {
-        if rel.as_os_str().is_empty() {
-            "~".to_string()
-        } else {
-            format!("~{}{}", std::path::MAIN_SEPARATOR, rel.display())
-        }
-    } else {
-        directory.display().to_string()
-    };
-
-    if let Some(max_width) = max_width {
-        if max_width == 0 {
-            return String::new();
-        }
-        if UnicodeWidthStr::width(formatted.as_str()) > max_width {
-            return text_formatting::center_truncate_path(&formatted, max_width);
-        }
-    }
-
-    formatted
-}
-
 pub(crate) fn format_reset_timestamp(dt: DateTime<Local>, captured_at: DateTime<Local>) -> String {
     let time = dt.format("%H:%M").to_string();
     if dt.date_naive() == captured_at.date_naive() {
@@ -13,8 +13,23 @@ pub fn user_message_style(terminal_bg: Option<(u8, u8, u8)>) -> Style {
     }
 }
 
-#[allow(clippy::disallowed_methods)]
+/// Returns the style for alternate (striped) cells in the transcript overlay,
+/// a fainter tint than user messages.
+pub fn transcript_stripe_style(terminal_bg: Option<(u8, u8, u8)>) -> Style {
+    match terminal_bg {
+        Some(bg) => Style::default().bg(tinted_bg(bg, 0.05)),
+        None => Style::default(),
+    }
+}
+
 pub fn user_message_bg(terminal_bg: (u8, u8, u8)) -> Color {
+    tinted_bg(terminal_bg, 0.1)
+}
+
+/// Blend the terminal background toward black or white by `alpha`, mapped to
+/// the best color the terminal supports.
+#[allow(clippy::disallowed_methods)]
+fn tinted_bg(terminal_bg: (u8, u8, u8), alpha: f32) -> Color {
     let top = if is_light(terminal_bg) {
         (0, 0, 0)
     } else {
@@ -25,7 +40,7 @@ pub fn user_message_bg(terminal_bg: (u8, u8, u8)) -> Color {
         return Color::default();
     };
 
-    let target = blend(top, bottom, 0.1);
+    let target = blend(top, bottom, alpha);
     if color_level.has_16m {
         let (r, g, b) = target;
         Color::Rgb(r, g, b)
@@ -19,11 +19,14 @@ use crossterm::SynchronizedUpdate;
 use crossterm::cursor::MoveTo;
 use crossterm::event::DisableBracketedPaste;
 use crossterm::event::DisableFocusChange;
+use crossterm::event::DisableMouseCapture;
 use crossterm::event::EnableBracketedPaste;
 use crossterm::event::EnableFocusChange;
+use crossterm::event::EnableMouseCapture;
 use crossterm::event::Event;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyboardEnhancementFlags;
+use crossterm::event::MouseEvent;
 use crossterm::event::PopKeyboardEnhancementFlags;
 use crossterm::event::PushKeyboardEnhancementFlags;
 use crossterm::terminal::EnterAlternateScreen;
@@ -117,6 +120,7 @@ pub fn restore() -> Result<()> {
     let _ = execute!(stdout(), PopKeyboardEnhancementFlags);
     execute!(stdout(), DisableBracketedPaste)?;
     let _ = execute!(stdout(), DisableFocusChange);
+    let _ = execute!(stdout(), DisableMouseCapture);
     disable_raw_mode()?;
     let _ = execute!(stdout(), crossterm::cursor::Show);
     Ok(())
@@ -148,6 +152,10 @@ fn set_panic_hook() {
 pub enum TuiEvent {
     Key(KeyEvent),
     Paste(String),
+    /// Only delivered while mouse capture is on (see
+    /// [`Tui::set_mouse_capture`]); otherwise alternate scroll turns the wheel
+    /// into arrow keys.
+    Mouse(MouseEvent),
     Draw,
 }
 
@@ -163,6 +171,8 @@ pub struct Tui {
     suspend_cursor_y: Arc<AtomicU16>, // Bottom line of inline viewport
     // True when overlay alt-screen UI is active
     alt_screen_active: Arc<AtomicBool>,
+    // True while the terminal reports mouse events, e.g. for pager scrolling
+    mouse_capture_active: bool,
     // True when terminal/tab is focused; updated internally from crossterm events
     terminal_focused: Arc<AtomicBool>,
     enhanced_keys_supported: bool,
@@ -291,6 +301,7 @@ impl Tui {
             #[cfg(unix)]
             suspend_cursor_y: Arc::new(AtomicU16::new(0)),
             alt_screen_active: Arc::new(AtomicBool::new(false)),
+            mouse_capture_active: false,
             terminal_focused: Arc::new(AtomicBool::new(true)),
             enhanced_keys_supported,
         }
@@ -360,13 +371,15 @@ impl Tui {
                             Event::Paste(pasted) => {
                                 yield TuiEvent::Paste(pasted);
                             }
+                            Event::Mouse(mouse_event) => {
+                                yield TuiEvent::Mouse(mouse_event);
+                            }
                             Event::FocusGained => {
                                 terminal_focused.store(true, Ordering::Relaxed);
                             }
                             Event::FocusLost => {
                                 terminal_focused.store(false, Ordering::Relaxed);
                             }
-                            _ => {}
                         }
                     }
                     result = draw_rx.recv() => {
@@ -433,6 +446,10 @@ impl Tui {
                 execute!(self.terminal.backend_mut(), EnterAlternateScreen)?;
                 // Enable "alternate scroll" so terminals may translate wheel to arrows
                 execute!(self.terminal.backend_mut(), EnableAlternateScroll)?;
+                // Suspending turned mouse capture off along with the other modes.
+                if self.mouse_capture_active {
+                    execute!(self.terminal.backend_mut(), EnableMouseCapture)?;
+                }
                 if let Ok(size) = self.terminal.size() {
                     self.terminal.set_viewport_area(ratatui::layout::Rect::new(
                         0,
@@ -479,6 +496,21 @@ impl Tui {
         Ok(())
     }
 
+    /// Have the terminal report mouse events, so wheel notches arrive as
+    /// [`TuiEvent::Mouse`] rather than as arrow keys. Native text selection
+    /// usually needs Shift while this is on.
+    pub fn set_mouse_capture(&mut self, enabled: bool) {
+        if enabled == self.mouse_capture_active {
+            return;
+        }
+        let _ = if enabled {
+            execute!(self.terminal.backend_mut(), EnableMouseCapture)
+        } else {
+            execute!(self.terminal.backend_mut(), DisableMouseCapture)
+        };
+        self.mouse_capture_active = enabled;
+    }
+
     pub fn insert_history_lines(&mut self, lines: Vec<Line<'static>>) {
         self.pending_history_lines.extend(lines);
         self.frame_requester().schedule_frame();